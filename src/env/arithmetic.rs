@@ -0,0 +1,151 @@
+use super::arity::arguments;
+use super::{Atom, Error, Number, Sexp};
+
+/// Collects arguments of numeric function, failing on anything that isn't
+/// a number.
+pub(super) fn numbers(sexp: &Sexp) -> Result<Vec<Number>, Error> {
+    sexp.into_iter()
+        .map(|arg| match arg {
+            Sexp::Atom(Atom::Number(n)) => Ok(n.clone()),
+            _ => Err(Error::Reason(format!("expected number, got: {}", arg))),
+        })
+        .collect()
+}
+
+pub(super) fn exactly(sexp: &Sexp, count: usize) -> Result<Vec<Number>, Error> {
+    let args = numbers(sexp)?;
+    arguments(sexp, count..=count)?;
+    Ok(args)
+}
+
+fn at_least_one(sexp: &Sexp) -> Result<Vec<Number>, Error> {
+    let args = numbers(sexp)?;
+    arguments(sexp, 1..)?;
+    Ok(args)
+}
+
+pub(super) fn number(n: Number) -> Sexp {
+    Sexp::Atom(Atom::Number(n))
+}
+
+pub fn add(sexp: &Sexp) -> Result<Sexp, Error> {
+    numbers(sexp)?
+        .iter()
+        .try_fold(Number::Int(0), |acc, n| acc.checked_add(n))
+        .map(number)
+}
+
+pub fn mul(sexp: &Sexp) -> Result<Sexp, Error> {
+    numbers(sexp)?
+        .iter()
+        .try_fold(Number::Int(1), |acc, n| acc.checked_mul(n))
+        .map(number)
+}
+
+/// `(- x)` negates `x`, `(- x y...)` subtracts every `y` from `x`.
+pub fn sub(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = at_least_one(sexp)?;
+    if args.len() == 1 {
+        return Number::Int(0).checked_sub(&args[0]).map(number);
+    }
+    args[1..]
+        .iter()
+        .try_fold(args[0].clone(), |acc, n| acc.checked_sub(n))
+        .map(number)
+}
+
+/// `(/ x)` is reciprocal of `x`, `(/ x y...)` divides `x` by every `y`.
+pub fn div(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = at_least_one(sexp)?;
+    if args.len() == 1 {
+        return Number::Int(1).checked_div(&args[0]).map(number);
+    }
+    args[1..]
+        .iter()
+        .try_fold(args[0].clone(), |acc, n| acc.checked_div(n))
+        .map(number)
+}
+
+pub fn quotient(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 2)?;
+    args[0].quotient(&args[1]).map(number)
+}
+
+pub fn remainder(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 2)?;
+    args[0].remainder(&args[1]).map(number)
+}
+
+pub fn modulo(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 2)?;
+    args[0].modulo(&args[1]).map(number)
+}
+
+pub fn abs(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].abs().map(number)
+}
+
+/// Picks the argument for which `pick(candidate, best)` holds. Result is
+/// inexact if any of the arguments is.
+fn extremum(sexp: &Sexp, pick: fn(&Number, &Number) -> bool) -> Result<Sexp, Error> {
+    let args = at_least_one(sexp)?;
+    for n in args.iter() {
+        n.real()?;
+    }
+    let mut best = &args[0];
+    for n in args[1..].iter() {
+        if n.to_f64().is_nan() || pick(n, best) {
+            best = n;
+        }
+    }
+    if args.iter().any(|n| !n.is_exact()) {
+        return Ok(number(best.to_inexact()));
+    }
+    Ok(number(best.clone()))
+}
+
+pub fn min(sexp: &Sexp) -> Result<Sexp, Error> {
+    extremum(sexp, |a, b| a < b)
+}
+
+pub fn max(sexp: &Sexp) -> Result<Sexp, Error> {
+    extremum(sexp, |a, b| a > b)
+}
+
+/// Numerator of the number in lowest terms, inexact for inexact argument.
+pub fn numerator(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    let exact = match args[0].to_exact()? {
+        Number::Rational(r) => Number::from(r.numerator().clone()),
+        n => n,
+    };
+    Ok(number(same_exactness(&args[0], exact)))
+}
+
+/// Denominator of the number in lowest terms, inexact for inexact argument.
+pub fn denominator(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    let exact = match args[0].to_exact()? {
+        Number::Rational(r) => Number::from(r.denominator().clone()),
+        _ => Number::Int(1),
+    };
+    Ok(number(same_exactness(&args[0], exact)))
+}
+
+fn same_exactness(like: &Number, n: Number) -> Number {
+    if like.is_exact() {
+        return n;
+    }
+    n.to_inexact()
+}
+
+pub fn exact_to_inexact(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].to_inexact()))
+}
+
+pub fn inexact_to_exact(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].to_exact().map(number)
+}
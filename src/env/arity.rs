@@ -0,0 +1,33 @@
+use super::{Error, Sexp};
+use std::ops::{Bound, RangeBounds};
+
+/// Collects arguments of a builtin, failing unless there are as many as
+/// `count` allows.
+pub(super) fn arguments(sexp: &Sexp, count: impl RangeBounds<usize>) -> Result<Vec<&Sexp>, Error> {
+    let args: Vec<&Sexp> = sexp.into_iter().collect();
+    if count.contains(&args.len()) {
+        return Ok(args);
+    }
+
+    let min = match count.start_bound() {
+        Bound::Included(min) => *min,
+        Bound::Excluded(min) => min + 1,
+        Bound::Unbounded => 0,
+    };
+    let max = match count.end_bound() {
+        Bound::Included(max) => Some(*max),
+        Bound::Excluded(max) => Some(max - 1),
+        Bound::Unbounded => None,
+    };
+    let noun = if min == 1 { "argument" } else { "arguments" };
+    Err(Error::Reason(match max {
+        Some(max) if max == min => format!("function takes {} {}", min, noun),
+        Some(max) if max == min + 1 => format!("function takes {} or {} arguments", min, max),
+        Some(max) => format!("function takes from {} to {} arguments", min, max),
+        None => format!("function takes at least {} {}", min, noun),
+    }))
+}
+
+pub(super) fn one_arg(sexp: &Sexp) -> Result<&Sexp, Error> {
+    arguments(sexp, 1..=1).map(|args| args[0])
+}
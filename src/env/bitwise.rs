@@ -0,0 +1,46 @@
+use super::arithmetic::{exactly, number, numbers};
+use super::{Error, Number, Sexp};
+
+pub fn bitwise_and(sexp: &Sexp) -> Result<Sexp, Error> {
+    numbers(sexp)?
+        .iter()
+        .try_fold(Number::Int(-1), |acc, n| acc.bitwise_and(n))
+        .map(number)
+}
+
+pub fn bitwise_or(sexp: &Sexp) -> Result<Sexp, Error> {
+    numbers(sexp)?
+        .iter()
+        .try_fold(Number::Int(0), |acc, n| acc.bitwise_or(n))
+        .map(number)
+}
+
+pub fn bitwise_xor(sexp: &Sexp) -> Result<Sexp, Error> {
+    numbers(sexp)?
+        .iter()
+        .try_fold(Number::Int(0), |acc, n| acc.bitwise_xor(n))
+        .map(number)
+}
+
+pub fn bitwise_not(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].bitwise_not().map(number)
+}
+
+/// `(arithmetic-shift n count)` shifts left for positive `count` and right
+/// for negative one.
+pub fn arithmetic_shift(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 2)?;
+    args[0].arithmetic_shift(&args[1]).map(number)
+}
+
+pub fn bit_count(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].bit_count().map(number)
+}
+
+/// `(bit-field n start end)` extracts bits `start` to `end` (exclusive).
+pub fn bit_field(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 3)?;
+    args[0].bit_field(&args[1], &args[2]).map(number)
+}
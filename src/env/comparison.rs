@@ -0,0 +1,39 @@
+use super::arithmetic::numbers;
+use super::arity::arguments;
+use super::{Atom, Error, Number, Sexp};
+
+/// Checks that `holds` is true for every pair of adjacent arguments, as in
+/// `(< 1 2 3)`. All arguments must be numbers, even after the chain breaks,
+/// and real numbers when `ordered` is set.
+fn chain(sexp: &Sexp, ordered: bool, holds: fn(&Number, &Number) -> bool) -> Result<Sexp, Error> {
+    let args = numbers(sexp)?;
+    arguments(sexp, 1..)?;
+    if ordered {
+        for n in args.iter() {
+            n.real()?;
+        }
+    }
+
+    let result = args.windows(2).all(|pair| holds(&pair[0], &pair[1]));
+    Ok(Sexp::Atom(Atom::Bool(result)))
+}
+
+pub fn equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, false, |a, b| a == b)
+}
+
+pub fn less(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, |a, b| a < b)
+}
+
+pub fn greater(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, |a, b| a > b)
+}
+
+pub fn less_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, |a, b| a <= b)
+}
+
+pub fn greater_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, |a, b| a >= b)
+}
@@ -0,0 +1,32 @@
+use super::arithmetic::{exactly, number};
+use super::{Error, Number, Sexp};
+
+pub fn make_rectangular(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 2)?;
+    Number::make_rectangular(&args[0], &args[1]).map(number)
+}
+
+pub fn make_polar(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 2)?;
+    Number::make_polar(&args[0], &args[1]).map(number)
+}
+
+pub fn real_part(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].real_part()))
+}
+
+pub fn imag_part(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].imag_part()))
+}
+
+pub fn magnitude(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].magnitude().map(number)
+}
+
+pub fn angle(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].angle()))
+}
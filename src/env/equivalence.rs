@@ -0,0 +1,35 @@
+use super::arity::arguments;
+use super::{Atom, Error, Sexp};
+use std::rc::Rc;
+
+/// Passing a cons around shares its car and cdr, so a cons is `eqv?` to
+/// itself, but not to another cons with the same contents.
+pub(super) fn eqv(a: &Sexp, b: &Sexp) -> bool {
+    match (a, b) {
+        (Sexp::Atom(a), Sexp::Atom(b)) => a == b,
+        (
+            Sexp::Cons { car, cdr },
+            Sexp::Cons {
+                car: other_car,
+                cdr: other_cdr,
+            },
+        ) => Rc::ptr_eq(car, other_car) && Rc::ptr_eq(cdr, other_cdr),
+        _ => false,
+    }
+}
+
+/// Same as `eqv?`, numbers and characters are unboxed values.
+pub fn is_eq(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    Ok(Sexp::Atom(Atom::Bool(eqv(args[0], args[1]))))
+}
+
+pub fn is_eqv(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    Ok(Sexp::Atom(Atom::Bool(eqv(args[0], args[1]))))
+}
+
+pub fn is_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    Ok(Sexp::Atom(Atom::Bool(args[0] == args[1])))
+}
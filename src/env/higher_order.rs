@@ -0,0 +1,199 @@
+use super::arity::arguments;
+use super::lists::elements;
+use super::{Atom, Env, Error, Sexp};
+use crate::eval::call;
+
+/// Checks that `sexp` can be called, so passing something else fails even
+/// when it would never be called because the lists are empty.
+fn function(sexp: &Sexp) -> Result<&Sexp, Error> {
+    match sexp {
+        Sexp::Atom(Atom::Func { .. })
+        | Sexp::Atom(Atom::EnvFunc { .. })
+        | Sexp::Atom(Atom::Lambda(_)) => Ok(sexp),
+        _ => Err(Error::Reason(format!("expected function, got: {}", sexp))),
+    }
+}
+
+/// Transposes `lists` into rows holding the i-th element of every list, as
+/// many as the shortest list has elements.
+fn rows(lists: &[&Sexp]) -> Result<Vec<Vec<Sexp>>, Error> {
+    let columns = lists
+        .iter()
+        .map(|list| elements(list))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let len = columns.iter().map(Vec::len).min().unwrap_or(0);
+    Ok((0..len)
+        .map(|i| columns.iter().map(|column| column[i].clone()).collect())
+        .collect())
+}
+
+/// `(map f list...)` is list of `f` applied to elements at the same position
+/// in every list, up to the end of the shortest one.
+pub fn map(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..)?;
+    let f = function(args[0])?;
+    let results = rows(&args[1..])?
+        .into_iter()
+        .map(|row| call(f, row, env))
+        .collect::<Result<Vec<Sexp>, Error>>()?;
+    Ok(Sexp::list(results))
+}
+
+/// Same as `map`, but only for the side effects of `f`.
+pub fn for_each(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..)?;
+    let f = function(args[0])?;
+    for row in rows(&args[1..])? {
+        call(f, row, env)?;
+    }
+    Ok(Sexp::Atom(Atom::Nil))
+}
+
+/// `(filter pred list)` is list of elements for which `pred` is true.
+pub fn filter(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    let pred = function(args[0])?;
+    let mut kept = Vec::new();
+    for item in elements(args[1])? {
+        if call(pred, vec![item.clone()], env)?.is_truthy() {
+            kept.push(item.clone());
+        }
+    }
+    Ok(Sexp::list(kept))
+}
+
+/// `(reduce f default list)` combines elements as `(f element acc)` starting
+/// from the first one, `default` is only returned for an empty list.
+pub fn reduce(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 3..=3)?;
+    let f = function(args[0])?;
+    let items = elements(args[2])?;
+    let (first, rest) = match items.split_first() {
+        Some(split) => split,
+        None => return Ok(args[1].clone()),
+    };
+    rest.iter().try_fold((*first).clone(), |acc, item| {
+        call(f, vec![(*item).clone(), acc], env)
+    })
+}
+
+/// `(fold-left f init list...)` combines rows from the left as
+/// `(f acc element...)`.
+pub fn fold_left(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 3..)?;
+    let f = function(args[0])?;
+    rows(&args[2..])?
+        .into_iter()
+        .try_fold(args[1].clone(), |acc, mut row| {
+            row.insert(0, acc);
+            call(f, row, env)
+        })
+}
+
+/// `(fold-right f init list...)` combines rows from the right as
+/// `(f element... acc)`.
+pub fn fold_right(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 3..)?;
+    let f = function(args[0])?;
+    rows(&args[2..])?
+        .into_iter()
+        .rev()
+        .try_fold(args[1].clone(), |acc, mut row| {
+            row.push(acc);
+            call(f, row, env)
+        })
+}
+
+/// `(find pred list)` is the first element for which `pred` is true, `false`
+/// if there is none.
+pub fn find(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    let pred = function(args[0])?;
+    for item in elements(args[1])? {
+        if call(pred, vec![item.clone()], env)?.is_truthy() {
+            return Ok(item.clone());
+        }
+    }
+    Ok(Sexp::Atom(Atom::Bool(false)))
+}
+
+/// `(any pred list...)` is the first true result of `pred` on rows of the
+/// lists, `false` if there is none.
+pub fn any(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..)?;
+    let pred = function(args[0])?;
+    for row in rows(&args[1..])? {
+        let result = call(pred, row, env)?;
+        if result.is_truthy() {
+            return Ok(result);
+        }
+    }
+    Ok(Sexp::Atom(Atom::Bool(false)))
+}
+
+/// `(every pred list...)` is the last result of `pred` on rows of the lists
+/// if all of them are true, `true` for empty lists.
+pub fn every(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..)?;
+    let pred = function(args[0])?;
+    let mut result = Sexp::Atom(Atom::Bool(true));
+    for row in rows(&args[1..])? {
+        result = call(pred, row, env)?;
+        if !result.is_truthy() {
+            return Ok(result);
+        }
+    }
+    Ok(result)
+}
+
+/// `(apply f arg... list)` calls `f` with `arg`s followed by elements of
+/// `list`.
+pub fn apply(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..)?;
+    let f = function(args[0])?;
+    let (list, init) = args[1..].split_last().expect("arity checked above");
+    let mut call_args: Vec<Sexp> = init.iter().map(|arg| (*arg).clone()).collect();
+    call_args.extend(elements(list)?.into_iter().cloned());
+    call(f, call_args, env)
+}
+
+/// `(sort list less?)` is stable merge sort of `list`, where `(less? a b)`
+/// tells whether `a` goes before `b`.
+pub fn sort(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    let less = function(args[1])?;
+    let items: Vec<Sexp> = elements(args[0])?.into_iter().cloned().collect();
+    let is_less = |a: &Sexp, b: &Sexp| -> Result<bool, Error> {
+        Ok(call(less, vec![a.clone(), b.clone()], env)?.is_truthy())
+    };
+    Ok(Sexp::list(merge_sort(items, &is_less)?))
+}
+
+/// Comparator may fail, which rules out `slice::sort_by`.
+fn merge_sort<F>(mut items: Vec<Sexp>, less: &F) -> Result<Vec<Sexp>, Error>
+where
+    F: Fn(&Sexp, &Sexp) -> Result<bool, Error>,
+{
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+    let right = items.split_off(items.len() / 2);
+    let left = merge_sort(items, less)?;
+    let right = merge_sort(right, less)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // Takes from the right only when strictly less, keeping equal
+        // elements in their original order.
+        if less(b, a)? {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
@@ -0,0 +1,228 @@
+use super::arity::arguments;
+use super::equivalence::eqv;
+use super::{Atom, Error, Number, Sexp};
+use std::rc::Rc;
+
+/// Elements of proper list `sexp`, failing on atoms and dotted lists.
+pub(super) fn elements(sexp: &Sexp) -> Result<Vec<&Sexp>, Error> {
+    if !sexp.is_list() {
+        return Err(Error::Reason(format!("expected list, got: {}", sexp)));
+    }
+    Ok(sexp.into_iter().collect())
+}
+
+/// Non-negative exact integer used as position in a list.
+pub(super) fn index(sexp: &Sexp) -> Result<usize, Error> {
+    match sexp {
+        Sexp::Atom(Atom::Number(Number::Int(i))) if *i >= 0 => Ok(*i as usize),
+        _ => Err(Error::Reason(format!(
+            "expected non-negative index, got: {}",
+            sexp
+        ))),
+    }
+}
+
+fn boolean(b: bool) -> Sexp {
+    Sexp::Atom(Atom::Bool(b))
+}
+
+pub fn cons(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    Ok(Sexp::Cons {
+        car: Rc::new(args[0].clone()),
+        cdr: Rc::new(args[1].clone()),
+    })
+}
+
+fn split(sexp: &Sexp) -> Result<(&Sexp, &Sexp), Error> {
+    match sexp {
+        Sexp::Cons { car, cdr } => Ok((car, cdr)),
+        _ => Err(Error::Reason(format!("expected cons, got: {}", sexp))),
+    }
+}
+
+pub fn car(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=1)?;
+    split(args[0]).map(|(car, _)| car.clone())
+}
+
+pub fn cdr(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=1)?;
+    split(args[0]).map(|(_, cdr)| cdr.clone())
+}
+
+/// Follows `path` of `a` (car) and `d` (cdr) steps, applied right to left
+/// like the letters in the function name, so `"ad"` is `cadr`.
+fn cxr(sexp: &Sexp, path: &str) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=1)?;
+    let mut current = args[0];
+    for step in path.chars().rev() {
+        let (car, cdr) = split(current)?;
+        current = if step == 'a' { car } else { cdr };
+    }
+    Ok(current.clone())
+}
+
+macro_rules! define_cxr {
+    ($ ($ name : ident => $ path : expr), *) => {
+        $ (
+            pub fn $name(sexp: &Sexp) -> Result<Sexp, Error> {
+                cxr(sexp, $path)
+            }
+        ) *
+    };
+}
+
+define_cxr!(
+    caar => "aa", cadr => "ad", cdar => "da", cddr => "dd",
+    caaar => "aaa", caadr => "aad", cadar => "ada", caddr => "add",
+    cdaar => "daa", cdadr => "dad", cddar => "dda", cdddr => "ddd"
+);
+
+pub fn list(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(sexp.clone())
+}
+
+/// Whether the argument is a proper list, `nil` included.
+pub fn is_list(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=1)?;
+    Ok(boolean(args[0].is_list()))
+}
+
+pub fn length(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=1)?;
+    let length = elements(args[0])?.len();
+    Ok(Sexp::Atom(Atom::Number(Number::from(length as i64))))
+}
+
+/// Concatenates lists, the last argument is shared as the tail of the result
+/// and need not be a list, so `(append '(1) 2)` is `(1 . 2)`.
+pub fn append(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args: Vec<&Sexp> = sexp.into_iter().collect();
+    let (last, init) = match args.split_last() {
+        Some(split) => split,
+        None => return Ok(Sexp::Atom(Atom::Nil)),
+    };
+    let mut items = Vec::new();
+    for arg in init {
+        items.extend(elements(arg)?);
+    }
+    Ok(items
+        .into_iter()
+        .rfold((*last).clone(), |acc, x| Sexp::Cons {
+            car: Rc::new(x.clone()),
+            cdr: Rc::new(acc),
+        }))
+}
+
+pub fn reverse(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=1)?;
+    Ok(elements(args[0])?
+        .into_iter()
+        .fold(Sexp::Atom(Atom::Nil), |acc, x| Sexp::Cons {
+            car: Rc::new(x.clone()),
+            cdr: Rc::new(acc),
+        }))
+}
+
+/// Cdr of `list` taken `k` times.
+fn tail<'a>(list: &'a Sexp, k: &Sexp) -> Result<&'a Sexp, Error> {
+    let mut rest = list;
+    for _ in 0..index(k)? {
+        rest = match rest {
+            Sexp::Cons { cdr, .. } => cdr,
+            _ => {
+                return Err(Error::Reason(format!(
+                    "index {} out of range for: {}",
+                    k, list
+                )))
+            }
+        };
+    }
+    Ok(rest)
+}
+
+/// `(list-ref list k)` is element of `list` at zero based position `k`.
+pub fn list_ref(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    match tail(args[0], args[1])? {
+        Sexp::Cons { car, .. } => Ok((**car).clone()),
+        _ => Err(Error::Reason(format!(
+            "index {} out of range for: {}",
+            args[1], args[0]
+        ))),
+    }
+}
+
+/// `(list-tail list k)` is `list` without its first `k` elements.
+pub fn list_tail(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    tail(args[0], args[1]).cloned()
+}
+
+/// Last cons of non-empty list, which may be dotted.
+pub fn last_pair(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=1)?;
+    let mut pair = args[0];
+    split(pair)?;
+    while let Sexp::Cons { cdr, .. } = pair {
+        if let Sexp::Atom(_) = **cdr {
+            break;
+        }
+        pair = cdr;
+    }
+    Ok(pair.clone())
+}
+
+/// First tail of `list` whose car is `same` as `x`, `false` if there is
+/// none.
+fn find_tail(sexp: &Sexp, same: fn(&Sexp, &Sexp) -> bool) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    let (x, list) = (args[0], args[1]);
+    let mut rest = list;
+    while let Sexp::Cons { car, cdr } = rest {
+        if same(x, car) {
+            return Ok(rest.clone());
+        }
+        rest = cdr;
+    }
+    if !rest.is_nil() {
+        return Err(Error::Reason(format!("expected list, got: {}", list)));
+    }
+    Ok(boolean(false))
+}
+
+/// `(member x list)` compares elements with `equal?`.
+pub fn member(sexp: &Sexp) -> Result<Sexp, Error> {
+    find_tail(sexp, |a, b| a == b)
+}
+
+/// `(memq x list)` compares elements with `eq?`.
+pub fn memq(sexp: &Sexp) -> Result<Sexp, Error> {
+    find_tail(sexp, eqv)
+}
+
+/// First pair of association list whose car is `same` as `key`, `false` if
+/// there is none.
+fn find_pair(sexp: &Sexp, same: fn(&Sexp, &Sexp) -> bool) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    let (key, alist) = (args[0], args[1]);
+    for pair in elements(alist)? {
+        let (car, _) = split(pair)
+            .map_err(|_| Error::Reason(format!("expected association list, got: {}", alist)))?;
+        if same(key, car) {
+            return Ok(pair.clone());
+        }
+    }
+    Ok(boolean(false))
+}
+
+/// `(assoc key alist)` compares keys with `equal?`.
+pub fn assoc(sexp: &Sexp) -> Result<Sexp, Error> {
+    find_pair(sexp, |a, b| a == b)
+}
+
+/// `(assq key alist)` compares keys with `eq?`.
+pub fn assq(sexp: &Sexp) -> Result<Sexp, Error> {
+    find_pair(sexp, eqv)
+}
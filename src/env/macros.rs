@@ -0,0 +1,12 @@
+use super::arity::one_arg;
+use super::{Env, Error, Sexp};
+use crate::eval;
+
+pub fn macroexpand_1(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let arg = one_arg(sexp)?;
+    Ok(eval::macroexpand_1(arg, env)?.unwrap_or_else(|| arg.clone()))
+}
+
+pub fn macroexpand(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    eval::macroexpand(one_arg(sexp)?, env)
+}
@@ -0,0 +1,151 @@
+use super::arithmetic::{exactly, number, numbers};
+use super::arity::arguments;
+use super::strings::{string, text};
+use super::{Atom, Error, Number, Sexp};
+
+/// Collects arguments of function taking one required and one optional
+/// number.
+fn one_or_two(sexp: &Sexp) -> Result<Vec<Number>, Error> {
+    let args = numbers(sexp)?;
+    arguments(sexp, 1..=2)?;
+    Ok(args)
+}
+
+pub fn sqrt(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].sqrt()))
+}
+
+/// `(exact-integer-sqrt n)` is list `(s r)` with `n = s^2 + r`.
+pub fn exact_integer_sqrt(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    let (root, remainder) = args[0].exact_integer_sqrt()?;
+    Ok(Sexp::list(vec![number(root), number(remainder)]))
+}
+
+pub fn expt(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 2)?;
+    args[0].expt(&args[1]).map(number)
+}
+
+pub fn exp(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].exp()))
+}
+
+/// `(log z)` is natural logarithm of `z`, `(log z b)` is logarithm to base
+/// `b`.
+pub fn log(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = one_or_two(sexp)?;
+    match args.get(1) {
+        Some(base) => args[0].log(base).map(number),
+        None => Ok(number(args[0].ln())),
+    }
+}
+
+pub fn sin(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].sin()))
+}
+
+pub fn cos(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].cos()))
+}
+
+pub fn tan(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].tan()))
+}
+
+pub fn asin(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].asin()))
+}
+
+pub fn acos(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    Ok(number(args[0].acos()))
+}
+
+/// `(atan z)` is arctangent of `z`, `(atan y x)` is angle of point `(x, y)`.
+pub fn atan(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = one_or_two(sexp)?;
+    match args.get(1) {
+        Some(x) => args[0].atan2(x).map(number),
+        None => Ok(number(args[0].atan())),
+    }
+}
+
+pub fn floor(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].floor().map(number)
+}
+
+pub fn ceiling(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].ceiling().map(number)
+}
+
+pub fn round(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].round().map(number)
+}
+
+pub fn truncate(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    args[0].truncate().map(number)
+}
+
+pub fn gcd(sexp: &Sexp) -> Result<Sexp, Error> {
+    numbers(sexp)?
+        .iter()
+        .try_fold(Number::Int(0), |acc, n| acc.gcd(n))
+        .map(number)
+}
+
+pub fn lcm(sexp: &Sexp) -> Result<Sexp, Error> {
+    numbers(sexp)?
+        .iter()
+        .try_fold(Number::Int(1), |acc, n| acc.lcm(n))
+        .map(number)
+}
+
+/// Radix given as optional argument of number conversion, 10 by default.
+fn radix(arg: Option<&Sexp>) -> Result<u32, Error> {
+    match arg {
+        None => Ok(10),
+        Some(Sexp::Atom(Atom::Number(Number::Int(radix @ (2 | 8 | 10 | 16))))) => Ok(*radix as u32),
+        Some(arg) => Err(Error::Reason(format!(
+            "expected radix 2, 8, 10 or 16, got: {}",
+            arg
+        ))),
+    }
+}
+
+/// Splits arguments of number conversion into the value and optional radix.
+fn with_radix(sexp: &Sexp) -> Result<(&Sexp, u32), Error> {
+    let args = arguments(sexp, 1..=2)?;
+    Ok((args[0], radix(args.get(1).copied())?))
+}
+
+/// `(number->string z [radix])` writes `z` as a string.
+pub fn number_to_string(sexp: &Sexp) -> Result<Sexp, Error> {
+    let (arg, radix) = with_radix(sexp)?;
+    let n = match arg {
+        Sexp::Atom(Atom::Number(n)) => n,
+        _ => return Err(Error::Reason(format!("expected number, got: {}", arg))),
+    };
+    n.to_str_radix(radix).map(string)
+}
+
+/// `(string->number s [radix])` reads numeral from string `s`, it is `false`
+/// if `s` isn't a valid numeral.
+pub fn string_to_number(sexp: &Sexp) -> Result<Sexp, Error> {
+    let (arg, radix) = with_radix(sexp)?;
+    let text = text(arg)?;
+    Ok(match Number::parse(text, radix) {
+        Ok(n) => number(n),
+        Err(_) => Sexp::Atom(Atom::Bool(false)),
+    })
+}
@@ -1,13 +1,59 @@
 use super::number::Number;
-use super::sexp::{Atom, Error, Sexp};
+use super::sexp::{original_name, strip_rename, Atom, Error, Sexp};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::rc::{Rc, Weak};
 mod predicates;
 use predicates::{
-    is_atom, is_boolean, is_char, is_cons, is_float, is_function, is_integer, is_null, is_number,
-    is_symbol,
+    is_atom, is_boolean, is_char, is_cons, is_even, is_float, is_function, is_integer, is_negative,
+    is_null, is_number, is_odd, is_positive, is_string, is_symbol, is_zero,
 };
+mod arithmetic;
+use arithmetic::{
+    abs, add, denominator, div, exact_to_inexact, inexact_to_exact, max, min, modulo, mul,
+    numerator, quotient, remainder, sub,
+};
+mod arity;
+mod bitwise;
+use bitwise::{
+    arithmetic_shift, bit_count, bit_field, bitwise_and, bitwise_not, bitwise_or, bitwise_xor,
+};
+mod comparison;
+use comparison::{equal, greater, greater_equal, less, less_equal};
+mod complex;
+use complex::{angle, imag_part, magnitude, make_polar, make_rectangular, real_part};
+mod equivalence;
+use equivalence::{is_eq, is_equal, is_eqv};
+mod higher_order;
+use higher_order::{
+    any, apply, every, filter, find, fold_left, fold_right, for_each, map, reduce, sort,
+};
+mod lists;
+use lists::{
+    append, assoc, assq, caaar, caadr, caar, cadar, caddr, cadr, car, cdaar, cdadr, cdar, cddar,
+    cdddr, cddr, cdr, cons, is_list, last_pair, length, list, list_ref, list_tail, member, memq,
+    reverse,
+};
+mod macros;
+use macros::{macroexpand, macroexpand_1};
+mod math;
+use math::{
+    acos, asin, atan, ceiling, cos, exact_integer_sqrt, exp, expt, floor, gcd, lcm, log,
+    number_to_string, round, sin, sqrt, string_to_number, tan, truncate,
+};
+mod random;
+use random::{random, random_float, random_seed, shuffle, Random};
 mod special_forms;
-use special_forms::{clone_exp, cons};
+pub use special_forms::{special_form, SpecialForm};
+mod strings;
+use strings::{
+    display, list_to_string, string_append, string_ci_equal, string_ci_greater,
+    string_ci_greater_equal, string_ci_less, string_ci_less_equal, string_contains,
+    string_downcase, string_equal, string_foldcase, string_greater, string_greater_equal,
+    string_index, string_join, string_length, string_less, string_less_equal, string_prefix,
+    string_ref, string_replace, string_split, string_suffix, string_to_list, string_trim,
+    string_trim_left, string_trim_right, string_upcase, substring,
+};
 
 macro_rules! add_func_to_env {
     ($ name : expr, $ func : expr, $ env : expr) => {
@@ -21,37 +67,147 @@ macro_rules! add_func_to_env {
     };
 }
 
-pub struct Env<'a> {
-    pub data: HashMap<String, Sexp>,
-    pub outer: Option<&'a Env<'a>>,
+macro_rules! add_env_func_to_env {
+    ($ name : expr, $ func : expr, $ env : expr) => {
+        $env.insert(
+            $name.to_owned(),
+            Sexp::Atom(Atom::EnvFunc {
+                fun: $func,
+                name: $name,
+            }),
+        )
+    };
+}
+
+/// Handle to a single scope of bindings.
+///
+/// Frames are reference counted, cloning an `Env` yields another handle to
+/// the same frame. That lets closures keep their defining scope alive after
+/// the call that created it returns, and lets every holder observe mutations.
+#[derive(Clone)]
+pub struct Env(Rc<Frame>);
+
+struct Frame {
+    data: RefCell<HashMap<String, Sexp>>,
+    outer: Option<Env>,
+    context: Rc<Context>,
+}
+
+/// State of one interpreter, shared by every frame descending from the same
+/// outermost one.
+#[derive(Default)]
+struct Context {
+    random: RefCell<Random>,
+    /// Last id handed out to a macro scope or expansion.
+    ids: Cell<usize>,
+    /// Environments hygienic macros were defined in, by scope id. Frames
+    /// that are gone are pruned whenever a scope is added.
+    scopes: RefCell<HashMap<usize, Weak<Frame>>>,
 }
 
-impl<'a> Env<'a> {
-    pub fn new(outer: Option<&'a Env<'a>>) -> Env<'a> {
-        Env {
-            data: HashMap::new(),
+impl Env {
+    pub fn new(outer: Option<Env>) -> Env {
+        let context = match &outer {
+            Some(outer) => outer.0.context.clone(),
+            None => Rc::new(Context::default()),
+        };
+        Env(Rc::new(Frame {
+            data: RefCell::new(HashMap::new()),
             outer,
+            context,
+        }))
+    }
+
+    /// Looks `symbol` up in this frame and its ancestors. Symbol renamed by
+    /// hygienic macro expansion that isn't bound under its new name resolves
+    /// to the name it had before the rename, in the environment the macro was
+    /// defined in.
+    pub fn search(&self, symbol: &str) -> Result<Sexp, Error> {
+        if let Some(sexp) = self.lookup(symbol) {
+            return Ok(sexp);
+        } else if let Some((renamed, id)) = strip_rename(symbol) {
+            return self.scope(id).search(renamed);
         }
+
+        Err(Error::Reason(format!(
+            "symbol not found in environment: {}",
+            original_name(symbol)
+        )))
     }
-    pub fn search(&self, symbol: &String) -> Result<Sexp, Error> {
-        if let Some(sexp) = self.data.get(symbol) {
-            return Ok((*sexp).clone());
-        } else if let Some(outer) = self.outer {
-            return outer.search(symbol);
+
+    fn lookup(&self, symbol: &str) -> Option<Sexp> {
+        if let Some(sexp) = self.0.data.borrow().get(symbol) {
+            return Some(sexp.clone());
         }
+        self.0.outer.as_ref().and_then(|outer| outer.lookup(symbol))
+    }
+
+    /// Registers this environment as scope of a hygienic macro defined in it
+    /// and returns id of the scope.
+    pub fn define_scope(&self) -> usize {
+        let id = self.next_id();
+        let mut scopes = self.0.context.scopes.borrow_mut();
+        scopes.retain(|_, frame| frame.strong_count() > 0);
+        scopes.insert(id, Rc::downgrade(&self.0));
+        id
+    }
 
-        Err(Error::Reason(String::from(
-            "Symbol not found in environment.",
+    /// Id of a new macro expansion, distinct from every other one.
+    pub fn next_expansion(&self) -> usize {
+        self.next_id()
+    }
+
+    fn next_id(&self) -> usize {
+        let id = self.0.context.ids.get() + 1;
+        self.0.context.ids.set(id);
+        id
+    }
+
+    /// Environment registered as scope `id`, this one when it's gone.
+    fn scope(&self, id: usize) -> Env {
+        match self.0.context.scopes.borrow().get(&id).map(Weak::upgrade) {
+            Some(Some(frame)) => Env(frame),
+            _ => self.clone(),
+        }
+    }
+
+    /// Whether both handles refer to the same frame.
+    pub fn ptr_eq(&self, other: &Env) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Binds `symbol` in this frame, shadowing bindings from outer frames.
+    pub fn insert(&self, symbol: String, sexp: Sexp) {
+        self.0.data.borrow_mut().insert(symbol, sexp);
+    }
+
+    /// Rebinds `symbol` in the nearest frame that already defines it.
+    pub fn set(&self, symbol: &str, sexp: Sexp) -> Result<(), Error> {
+        if self.update(symbol, &sexp) {
+            return Ok(());
+        } else if let Some((renamed, id)) = strip_rename(symbol) {
+            return self.scope(id).set(renamed, sexp);
+        }
+
+        Err(Error::Reason(format!(
+            "cannot set unbound symbol: {}",
+            original_name(symbol)
         )))
     }
 
-    pub fn insert(&mut self, symbol: String, sexp: Sexp) {
-        self.data.insert(symbol, sexp);
+    fn update(&self, symbol: &str, sexp: &Sexp) -> bool {
+        if let Some(value) = self.0.data.borrow_mut().get_mut(symbol) {
+            *value = sexp.clone();
+            return true;
+        }
+        match &self.0.outer {
+            Some(outer) => outer.update(symbol, sexp),
+            None => false,
+        }
     }
 
-    pub fn default_env() -> Env<'a> {
-        let mut default = Env::new(None);
-        add_func_to_env!("'", clone_exp, default);
+    pub fn default_env() -> Env {
+        let default = Env::new(None);
         add_func_to_env!("atom?", is_atom, default);
         add_func_to_env!("bool?", is_boolean, default);
         add_func_to_env!("char?", is_char, default);
@@ -61,8 +217,141 @@ impl<'a> Env<'a> {
         add_func_to_env!("int?", is_integer, default);
         add_func_to_env!("null?", is_null, default);
         add_func_to_env!("number?", is_number, default);
+        add_func_to_env!("string?", is_string, default);
         add_func_to_env!("symbol?", is_symbol, default);
+        add_func_to_env!("zero?", is_zero, default);
+        add_func_to_env!("positive?", is_positive, default);
+        add_func_to_env!("negative?", is_negative, default);
+        add_func_to_env!("even?", is_even, default);
+        add_func_to_env!("odd?", is_odd, default);
         add_func_to_env!("cons", cons, default);
+        add_func_to_env!("car", car, default);
+        add_func_to_env!("cdr", cdr, default);
+        add_func_to_env!("caar", caar, default);
+        add_func_to_env!("cadr", cadr, default);
+        add_func_to_env!("cdar", cdar, default);
+        add_func_to_env!("cddr", cddr, default);
+        add_func_to_env!("caaar", caaar, default);
+        add_func_to_env!("caadr", caadr, default);
+        add_func_to_env!("cadar", cadar, default);
+        add_func_to_env!("caddr", caddr, default);
+        add_func_to_env!("cdaar", cdaar, default);
+        add_func_to_env!("cdadr", cdadr, default);
+        add_func_to_env!("cddar", cddar, default);
+        add_func_to_env!("cdddr", cdddr, default);
+        add_func_to_env!("list", list, default);
+        add_func_to_env!("list?", is_list, default);
+        add_func_to_env!("length", length, default);
+        add_func_to_env!("append", append, default);
+        add_func_to_env!("reverse", reverse, default);
+        add_func_to_env!("list-ref", list_ref, default);
+        add_func_to_env!("list-tail", list_tail, default);
+        add_func_to_env!("last-pair", last_pair, default);
+        add_func_to_env!("member", member, default);
+        add_func_to_env!("memq", memq, default);
+        add_func_to_env!("assoc", assoc, default);
+        add_func_to_env!("assq", assq, default);
+        add_func_to_env!("eq?", is_eq, default);
+        add_func_to_env!("eqv?", is_eqv, default);
+        add_func_to_env!("equal?", is_equal, default);
+        add_func_to_env!("+", add, default);
+        add_func_to_env!("-", sub, default);
+        add_func_to_env!("*", mul, default);
+        add_func_to_env!("/", div, default);
+        add_func_to_env!("quotient", quotient, default);
+        add_func_to_env!("remainder", remainder, default);
+        add_func_to_env!("modulo", modulo, default);
+        add_func_to_env!("abs", abs, default);
+        add_func_to_env!("min", min, default);
+        add_func_to_env!("max", max, default);
+        add_func_to_env!("numerator", numerator, default);
+        add_func_to_env!("denominator", denominator, default);
+        add_func_to_env!("exact->inexact", exact_to_inexact, default);
+        add_func_to_env!("inexact->exact", inexact_to_exact, default);
+        add_func_to_env!("make-rectangular", make_rectangular, default);
+        add_func_to_env!("make-polar", make_polar, default);
+        add_func_to_env!("real-part", real_part, default);
+        add_func_to_env!("imag-part", imag_part, default);
+        add_func_to_env!("magnitude", magnitude, default);
+        add_func_to_env!("angle", angle, default);
+        add_func_to_env!("sqrt", sqrt, default);
+        add_func_to_env!("exp", exp, default);
+        add_func_to_env!("log", log, default);
+        add_func_to_env!("expt", expt, default);
+        add_func_to_env!("exact-integer-sqrt", exact_integer_sqrt, default);
+        add_func_to_env!("sin", sin, default);
+        add_func_to_env!("cos", cos, default);
+        add_func_to_env!("tan", tan, default);
+        add_func_to_env!("asin", asin, default);
+        add_func_to_env!("acos", acos, default);
+        add_func_to_env!("atan", atan, default);
+        add_func_to_env!("floor", floor, default);
+        add_func_to_env!("ceiling", ceiling, default);
+        add_func_to_env!("round", round, default);
+        add_func_to_env!("truncate", truncate, default);
+        add_func_to_env!("gcd", gcd, default);
+        add_func_to_env!("lcm", lcm, default);
+        add_func_to_env!("number->string", number_to_string, default);
+        add_func_to_env!("string->number", string_to_number, default);
+        add_func_to_env!("=", equal, default);
+        add_func_to_env!("<", less, default);
+        add_func_to_env!(">", greater, default);
+        add_func_to_env!("<=", less_equal, default);
+        add_func_to_env!(">=", greater_equal, default);
+        add_func_to_env!("bitwise-and", bitwise_and, default);
+        add_func_to_env!("bitwise-or", bitwise_or, default);
+        add_func_to_env!("bitwise-xor", bitwise_xor, default);
+        add_func_to_env!("bitwise-not", bitwise_not, default);
+        add_func_to_env!("arithmetic-shift", arithmetic_shift, default);
+        add_func_to_env!("bit-count", bit_count, default);
+        add_func_to_env!("bit-field", bit_field, default);
+        add_func_to_env!("string->list", string_to_list, default);
+        add_func_to_env!("list->string", list_to_string, default);
+        add_func_to_env!("string-length", string_length, default);
+        add_func_to_env!("string-ref", string_ref, default);
+        add_func_to_env!("substring", substring, default);
+        add_func_to_env!("string-append", string_append, default);
+        add_env_func_to_env!("string-index", string_index, default);
+        add_func_to_env!("string-contains", string_contains, default);
+        add_func_to_env!("string-upcase", string_upcase, default);
+        add_func_to_env!("string-downcase", string_downcase, default);
+        add_func_to_env!("string-foldcase", string_foldcase, default);
+        add_func_to_env!("string-split", string_split, default);
+        add_func_to_env!("string-join", string_join, default);
+        add_func_to_env!("string-trim", string_trim, default);
+        add_func_to_env!("string-trim-left", string_trim_left, default);
+        add_func_to_env!("string-trim-right", string_trim_right, default);
+        add_func_to_env!("string-prefix?", string_prefix, default);
+        add_func_to_env!("string-suffix?", string_suffix, default);
+        add_func_to_env!("string-replace", string_replace, default);
+        add_func_to_env!("string=?", string_equal, default);
+        add_func_to_env!("string<?", string_less, default);
+        add_func_to_env!("string>?", string_greater, default);
+        add_func_to_env!("string<=?", string_less_equal, default);
+        add_func_to_env!("string>=?", string_greater_equal, default);
+        add_func_to_env!("string-ci=?", string_ci_equal, default);
+        add_func_to_env!("string-ci<?", string_ci_less, default);
+        add_func_to_env!("string-ci>?", string_ci_greater, default);
+        add_func_to_env!("string-ci<=?", string_ci_less_equal, default);
+        add_func_to_env!("string-ci>=?", string_ci_greater_equal, default);
+        add_func_to_env!("display", display, default);
+        add_env_func_to_env!("map", map, default);
+        add_env_func_to_env!("for-each", for_each, default);
+        add_env_func_to_env!("filter", filter, default);
+        add_env_func_to_env!("reduce", reduce, default);
+        add_env_func_to_env!("fold-left", fold_left, default);
+        add_env_func_to_env!("fold-right", fold_right, default);
+        add_env_func_to_env!("find", find, default);
+        add_env_func_to_env!("any", any, default);
+        add_env_func_to_env!("every", every, default);
+        add_env_func_to_env!("apply", apply, default);
+        add_env_func_to_env!("sort", sort, default);
+        add_env_func_to_env!("random", random, default);
+        add_env_func_to_env!("random-float", random_float, default);
+        add_env_func_to_env!("random-seed!", random_seed, default);
+        add_env_func_to_env!("shuffle", shuffle, default);
+        add_env_func_to_env!("macroexpand-1", macroexpand_1, default);
+        add_env_func_to_env!("macroexpand", macroexpand, default);
 
         default
     }
@@ -43,6 +43,20 @@ pub fn is_char(sexp: &Sexp) -> Result<Sexp, Error> {
 
     Ok(Sexp::Atom(Atom::Bool(false)))
 }
+pub fn is_string(sexp: &Sexp) -> Result<Sexp, Error> {
+    let mut iter = sexp.into_iter();
+    let arg = iter.next();
+    let rest = iter.next();
+    if arg.is_none() || rest.is_some() {
+        return Err(Error::Reason("function takes 1 argument".to_owned()));
+    }
+
+    if let Sexp::Atom(Atom::String(_)) = arg.expect("empty value after check") {
+        return Ok(Sexp::Atom(Atom::Bool(true)));
+    }
+
+    Ok(Sexp::Atom(Atom::Bool(false)))
+}
 pub fn is_integer(sexp: &Sexp) -> Result<Sexp, Error> {
     let mut iter = sexp.into_iter();
     let arg = iter.next();
@@ -51,7 +65,9 @@ pub fn is_integer(sexp: &Sexp) -> Result<Sexp, Error> {
         return Err(Error::Reason("function takes 1 argument".to_owned()));
     }
 
-    if let Sexp::Atom(Atom::Number(Number::Int(_))) = arg.expect("empty value after check") {
+    if let Sexp::Atom(Atom::Number(Number::Int(_))) | Sexp::Atom(Atom::Number(Number::Big(_))) =
+        arg.expect("empty value after check")
+    {
         return Ok(Sexp::Atom(Atom::Bool(true)));
     }
 
@@ -118,7 +134,6 @@ pub fn is_symbol(sexp: &Sexp) -> Result<Sexp, Error> {
     Ok(Sexp::Atom(Atom::Bool(false)))
 }
 pub fn is_function(sexp: &Sexp) -> Result<Sexp, Error> {
-    // TODO: update after implementing lambdas
     let mut iter = sexp.into_iter();
     let arg = iter.next();
     let rest = iter.next();
@@ -126,7 +141,10 @@ pub fn is_function(sexp: &Sexp) -> Result<Sexp, Error> {
         return Err(Error::Reason("function takes 1 argument".to_owned()));
     }
 
-    if let Sexp::Atom(Atom::Func { .. }) = arg.expect("empty value after check") {
+    if let Sexp::Atom(Atom::Func { .. })
+    | Sexp::Atom(Atom::EnvFunc { .. })
+    | Sexp::Atom(Atom::Lambda(_)) = arg.expect("empty value after check")
+    {
         return Ok(Sexp::Atom(Atom::Bool(true)));
     }
 
@@ -146,3 +164,41 @@ pub fn is_null(sexp: &Sexp) -> Result<Sexp, Error> {
 
     Ok(Sexp::Atom(Atom::Bool(false)))
 }
+
+fn numeric_arg(sexp: &Sexp) -> Result<&Number, Error> {
+    let mut iter = sexp.into_iter();
+    let arg = iter.next();
+    let rest = iter.next();
+    if arg.is_none() || rest.is_some() {
+        return Err(Error::Reason("function takes 1 argument".to_owned()));
+    }
+
+    match arg.expect("empty value after check") {
+        Sexp::Atom(Atom::Number(n)) => Ok(n),
+        other => Err(Error::Reason(format!("expected number, got: {}", other))),
+    }
+}
+
+pub fn is_zero(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(Sexp::Atom(Atom::Bool(numeric_arg(sexp)?.is_zero())))
+}
+
+pub fn is_positive(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(Sexp::Atom(Atom::Bool(
+        numeric_arg(sexp)?.real()?.is_positive(),
+    )))
+}
+
+pub fn is_negative(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(Sexp::Atom(Atom::Bool(
+        numeric_arg(sexp)?.real()?.is_negative(),
+    )))
+}
+
+pub fn is_even(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(Sexp::Atom(Atom::Bool(numeric_arg(sexp)?.is_even()?)))
+}
+
+pub fn is_odd(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(Sexp::Atom(Atom::Bool(!numeric_arg(sexp)?.is_even()?)))
+}
@@ -0,0 +1,134 @@
+use super::arithmetic::{exactly, number};
+use super::arity::one_arg;
+use super::lists::elements;
+use super::{Atom, Env, Error, Number, Sexp};
+use crate::number::BigInt;
+
+/// Seed of the generator of a fresh interpreter, so programs that never call
+/// `random-seed!` are reproducible too.
+const DEFAULT_SEED: u64 = 0x5eed;
+
+/// xoshiro256** generator, seeded by expanding a single 64 bit seed with
+/// splitmix64.
+pub(super) struct Random {
+    state: [u64; 4],
+}
+
+impl Random {
+    pub(super) fn new(seed: u64) -> Random {
+        let mut seed = seed;
+        let mut state = [0; 4];
+        for word in state.iter_mut() {
+            seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *word = z ^ (z >> 31);
+        }
+        Random { state }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+        result
+    }
+
+    /// Uniform float in `[0, 1)` made of the top 53 bits.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform integer in `[0, bound)`, rejecting samples from the partial
+    /// range at the top to avoid modulo bias.
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let sample = self.next_u64();
+            if sample < zone {
+                return sample % bound;
+            }
+        }
+    }
+
+    /// Uniform integer in `[0, bound)` for positive `bound` of any size,
+    /// drawing as many bits as `bound` has until the sample is below it.
+    fn below_big(&mut self, bound: &BigInt) -> BigInt {
+        let bits = bound.bit_length();
+        loop {
+            let mut sample = BigInt::zero();
+            for _ in 0..bits.div_ceil(32) {
+                let digit = BigInt::from(i64::from(self.next_u64() as u32));
+                sample = sample.shl(32).add(&digit);
+            }
+            let sample = sample.shr(bits.div_ceil(32) * 32 - bits);
+            if sample < *bound {
+                return sample;
+            }
+        }
+    }
+}
+
+impl Default for Random {
+    fn default() -> Random {
+        Random::new(DEFAULT_SEED)
+    }
+}
+
+/// `(random n)` is uniform exact integer from 0 up to, but excluding, `n`.
+pub fn random(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    let mut random = env.0.context.random.borrow_mut();
+    match &args[0] {
+        Number::Int(bound) if *bound > 0 => {
+            Ok(number(Number::Int(random.below(*bound as u64) as i32)))
+        }
+        Number::Big(bound) if !bound.is_negative() => {
+            Ok(number(Number::from(random.below_big(bound))))
+        }
+        n => Err(Error::Reason(format!(
+            "expected exact positive integer, got: {}",
+            n
+        ))),
+    }
+}
+
+/// `(random-float)` is uniform float from 0 up to, but excluding, 1.
+pub fn random_float(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    exactly(sexp, 0)?;
+    let f = env.0.context.random.borrow_mut().next_f64();
+    Ok(number(Number::Float(f)))
+}
+
+/// `(random-seed! n)` restarts the generator from exact integer seed `n`.
+pub fn random_seed(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = exactly(sexp, 1)?;
+    let seed = args[0]
+        .to_big()
+        .and_then(|seed| seed.to_i64())
+        .ok_or_else(|| {
+            Error::Reason(format!(
+                "expected exact integer seed that fits in 64 bits, got: {}",
+                args[0]
+            ))
+        })?;
+    *env.0.context.random.borrow_mut() = Random::new(seed as u64);
+    Ok(Sexp::Atom(Atom::Nil))
+}
+
+/// `(shuffle list)` is new list with elements of `list` in random order.
+pub fn shuffle(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let mut items: Vec<Sexp> = elements(one_arg(sexp)?)?.into_iter().cloned().collect();
+    let mut random = env.0.context.random.borrow_mut();
+    for i in (1..items.len()).rev() {
+        let j = random.below(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+    Ok(Sexp::list(items))
+}
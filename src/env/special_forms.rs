@@ -1,27 +1,645 @@
-use super::{Error, Sexp};
+use super::Env;
+use crate::eval::{eval_body, evaluate, Tail};
+use crate::sexp::{original_name, Atom, Error, Lambda, Sexp};
+use crate::syntax_rules::SyntaxRules;
+use std::rc::Rc;
 
-pub fn clone_exp(sexp: &Sexp) -> Result<Sexp, Error> {
-    let mut iter = sexp.into_iter();
+/// Special forms receive their arguments unevaluated together with the
+/// environment of the call site.
+///
+/// Expression left in tail position is returned as `Tail` rather than
+/// evaluated, so the evaluator can continue with it without growing the stack.
+pub type SpecialForm = fn(&Sexp, &Env) -> Result<Tail, Error>;
+
+/// Looks up special form bound to `name`.
+///
+/// Special forms are resolved before the environment is consulted and their
+/// names can't be rebound, so they can't be shadowed by accident.
+pub fn special_form(name: &str) -> Option<SpecialForm> {
+    match name {
+        "quote" => Some(quote),
+        "quasiquote" => Some(quasiquote),
+        "unquote" => Some(unquote),
+        "unquote-splicing" => Some(unquote),
+        "lambda" => Some(lambda),
+        "define" => Some(define),
+        "set!" => Some(set),
+        "defmacro" => Some(defmacro),
+        "define-syntax" => Some(define_syntax),
+        "syntax-rules" => Some(syntax_rules),
+        "if" => Some(eval_if),
+        "cond" => Some(cond),
+        "when" => Some(when),
+        "unless" => Some(unless),
+        "and" => Some(and),
+        "or" => Some(or),
+        "let" => Some(eval_let),
+        "let*" => Some(let_star),
+        "letrec" => Some(letrec),
+        "letrec*" => Some(letrec_star),
+        _ => None,
+    }
+}
+
+/// Fails when `name` can't be bound because it names a special form.
+pub fn check_bindable(name: &str) -> Result<(), Error> {
+    if special_form(name).is_some() {
+        return Err(Error::Reason(format!(
+            "cannot bind special form name: {}",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// `(quote exp)` returns `exp` unevaluated.
+fn quote(exp: &Sexp, _env: &Env) -> Result<Tail, Error> {
+    Ok(Tail::Value(single_arg("quote", exp)?.clone()))
+}
+
+fn single_arg<'a>(form: &str, exp: &'a Sexp) -> Result<&'a Sexp, Error> {
+    let mut iter = exp.into_iter();
     let arg = iter.next();
     let rest = iter.next();
     if arg.is_none() || rest.is_some() {
-        return Err(Error::Reason("function takes 1 argument".to_owned()));
+        return Err(Error::Reason(format!("{} takes 1 argument", form)));
     }
 
-    Ok(arg.expect("missing argument after check").clone())
+    Ok(arg.expect("missing argument after check"))
 }
 
-pub fn cons(sexp: &Sexp) -> Result<Sexp, Error> {
-    let mut iter = sexp.into_iter();
-    let arg1 = iter.next();
-    let arg2 = iter.next();
-    let rest = iter.next();
-    if arg1.is_none() || arg2.is_none() || rest.is_some() {
-        return Err(Error::Reason("function takes 2 arguments".to_owned()));
+/// `(quasiquote template)` returns `template` unevaluated, except for
+/// `(unquote exp)` parts, which are replaced with value of `exp`, and
+/// `(unquote-splicing exp)` parts, whose list value is spliced into the
+/// enclosing list. Nested quasiquotes raise the level, unquotes only
+/// evaluate at the outermost one.
+fn quasiquote(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    let template = single_arg("quasiquote", exp)?;
+    Ok(Tail::Value(expand_quasiquote(template, 1, env)?))
+}
+
+fn unquote(_exp: &Sexp, _env: &Env) -> Result<Tail, Error> {
+    Err(Error::Reason(
+        "unquote used outside of quasiquote".to_owned(),
+    ))
+}
+
+fn expand_quasiquote(template: &Sexp, depth: usize, env: &Env) -> Result<Sexp, Error> {
+    let (car, cdr) = match template {
+        Sexp::Atom(_) => return Ok(template.clone()),
+        Sexp::Cons { car, cdr } => (&**car, &**cdr),
+    };
+
+    match quotation(template) {
+        Some(("unquote", exp)) if depth == 1 => return evaluate(exp, env),
+        Some(("unquote", exp)) => return requote("unquote", exp, depth - 1, env),
+        Some(("quasiquote", exp)) => return requote("quasiquote", exp, depth + 1, env),
+        _ => {}
+    }
+
+    match quotation(car) {
+        Some(("unquote-splicing", exp)) if depth == 1 => {
+            let spliced = evaluate(exp, env)?;
+            let rest = expand_quasiquote(cdr, depth, env)?;
+            append(&spliced, rest)
+        }
+        Some(("unquote-splicing", exp)) => Ok(Sexp::Cons {
+            car: Rc::new(requote("unquote-splicing", exp, depth - 1, env)?),
+            cdr: Rc::new(expand_quasiquote(cdr, depth, env)?),
+        }),
+        _ => Ok(Sexp::Cons {
+            car: Rc::new(expand_quasiquote(car, depth, env)?),
+            cdr: Rc::new(expand_quasiquote(cdr, depth, env)?),
+        }),
+    }
+}
+
+/// Splits `(form exp)` into its parts when `form` is one of quotation
+/// symbols.
+fn quotation(sexp: &Sexp) -> Option<(&str, &Sexp)> {
+    if let Sexp::Cons { car, cdr } = sexp {
+        if let (
+            Sexp::Atom(Atom::Symbol(form)),
+            Sexp::Cons {
+                car: exp,
+                cdr: rest,
+            },
+        ) = (&**car, &**cdr)
+        {
+            let quoting = matches!(form.as_str(), "quasiquote" | "unquote" | "unquote-splicing");
+            if quoting && rest.is_nil() {
+                return Some((form, exp));
+            }
+        }
+    }
+    None
+}
+
+fn requote(form: &str, exp: &Sexp, depth: usize, env: &Env) -> Result<Sexp, Error> {
+    Ok(Sexp::list(vec![
+        Sexp::Atom(Atom::Symbol(form.to_owned())),
+        expand_quasiquote(exp, depth, env)?,
+    ]))
+}
+
+/// Prepends items of proper list `list` to `tail`.
+fn append(list: &Sexp, tail: Sexp) -> Result<Sexp, Error> {
+    let mut items = Vec::new();
+    let mut rest = list;
+    while let Sexp::Cons { car, cdr } = rest {
+        items.push((**car).clone());
+        rest = cdr;
+    }
+    if !rest.is_nil() {
+        return Err(Error::Reason(format!(
+            "unquote-splicing expects a list, got: {}",
+            list
+        )));
+    }
+
+    Ok(items.into_iter().rfold(tail, |acc, x| Sexp::Cons {
+        car: Rc::new(x),
+        cdr: Rc::new(acc),
+    }))
+}
+
+fn lambda(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    if let Sexp::Cons { car, cdr } = exp {
+        let lambda = new_lambda(car, cdr, env)?;
+        return Ok(Tail::Value(Sexp::Atom(Atom::Lambda(lambda))));
+    }
+
+    Err(Error::Reason(
+        "lambda takes a parameter list and a body".to_owned(),
+    ))
+}
+
+fn new_lambda(params: &Sexp, body: &Sexp, env: &Env) -> Result<Lambda, Error> {
+    if let Sexp::Cons { .. } = body {
+        check_params(params)?;
+        return Ok(Lambda {
+            params_exp: Rc::new(params.clone()),
+            body_exp: Rc::new(body.clone()),
+            env: env.clone(),
+        });
+    }
+
+    Err(Error::Reason(
+        "lambda takes a parameter list and a body".to_owned(),
+    ))
+}
+
+/// `(define name expr)` binds value of `expr` in the current frame,
+/// `(define (name params...) body...)` is a shorthand for binding a lambda.
+fn define(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    if let Sexp::Cons { car, cdr } = exp {
+        match &**car {
+            Sexp::Atom(Atom::Symbol(name)) => {
+                check_bindable(name)?;
+                let value = match &**cdr {
+                    Sexp::Cons {
+                        car: value,
+                        cdr: rest,
+                    } if rest.is_nil() => evaluate(value, env)?,
+                    _ => {
+                        return Err(Error::Reason(format!(
+                            "define takes a symbol and a single expression: {}",
+                            name
+                        )))
+                    }
+                };
+                env.insert(name.clone(), value);
+                return Ok(Tail::Value(Sexp::Atom(Atom::Symbol(name.clone()))));
+            }
+            Sexp::Cons {
+                car: name,
+                cdr: params,
+            } => {
+                if let Sexp::Atom(Atom::Symbol(name)) = &**name {
+                    check_bindable(name)?;
+                    let lambda = new_lambda(params, cdr, env)?;
+                    env.insert(name.clone(), Sexp::Atom(Atom::Lambda(lambda)));
+                    return Ok(Tail::Value(Sexp::Atom(Atom::Symbol(name.clone()))));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Err(Error::Reason(
+        "define takes a symbol or a (name params...) list".to_owned(),
+    ))
+}
+
+/// `(set! name expr)` rebinds an existing binding in the nearest frame that
+/// defines it.
+fn set(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    if let Sexp::Cons { car, cdr } = exp {
+        if let (
+            Sexp::Atom(Atom::Symbol(name)),
+            Sexp::Cons {
+                car: value,
+                cdr: rest,
+            },
+        ) = (&**car, &**cdr)
+        {
+            if rest.is_nil() {
+                check_bindable(name)?;
+                let value = evaluate(value, env)?;
+                env.set(name, value.clone())?;
+                return Ok(Tail::Value(value));
+            }
+        }
+    }
+
+    Err(Error::Reason(
+        "set! takes a symbol and a single expression".to_owned(),
+    ))
+}
+
+/// `(defmacro name (params...) body...)` binds a macro transformer. When
+/// `(name args...)` is evaluated, body is evaluated with unevaluated `args`
+/// bound to `params` and its value is evaluated in place of the call.
+fn defmacro(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    if let Sexp::Cons { car, cdr } = exp {
+        if let (
+            Sexp::Atom(Atom::Symbol(name)),
+            Sexp::Cons {
+                car: params,
+                cdr: body,
+            },
+        ) = (&**car, &**cdr)
+        {
+            check_bindable(name)?;
+            let transformer = new_lambda(params, body, env)?;
+            env.insert(name.clone(), Sexp::Atom(Atom::Macro(transformer)));
+            return Ok(Tail::Value(Sexp::Atom(Atom::Symbol(name.clone()))));
+        }
+    }
+
+    Err(Error::Reason(
+        "defmacro takes a name, a parameter list and a body".to_owned(),
+    ))
+}
+
+/// `(define-syntax name transformer)` binds a transformer created by
+/// `syntax-rules`.
+fn define_syntax(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    if let Sexp::Cons { car, cdr } = exp {
+        if let (
+            Sexp::Atom(Atom::Symbol(name)),
+            Sexp::Cons {
+                car: spec,
+                cdr: rest,
+            },
+        ) = (&**car, &**cdr)
+        {
+            if rest.is_nil() {
+                check_bindable(name)?;
+                let transformer = evaluate(spec, env)?;
+                if let Sexp::Atom(Atom::Syntax(_)) = transformer {
+                    env.insert(name.clone(), transformer);
+                    return Ok(Tail::Value(Sexp::Atom(Atom::Symbol(name.clone()))));
+                }
+                return Err(Error::Reason(format!(
+                    "define-syntax expects a syntax transformer, got: {}",
+                    transformer
+                )));
+            }
+        }
+    }
+
+    Err(Error::Reason(
+        "define-syntax takes a name and a transformer".to_owned(),
+    ))
+}
+
+/// `(syntax-rules (literals...) (pattern template)...)` creates a hygienic
+/// pattern based macro transformer.
+fn syntax_rules(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    let rules = SyntaxRules::parse(exp, env)?;
+    Ok(Tail::Value(Sexp::Atom(Atom::Syntax(Rc::new(rules)))))
+}
+
+/// `(if test then else)`, missing `else` branch evaluates to `nil`.
+fn eval_if(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    let args: Vec<&Sexp> = exp.into_iter().collect();
+    if args.len() != 2 && args.len() != 3 {
+        return Err(Error::Reason(
+            "if takes a test, a consequent and an optional alternative".to_owned(),
+        ));
+    }
+
+    if evaluate(args[0], env)?.is_truthy() {
+        Ok(Tail::Eval(args[1].clone(), env.clone()))
+    } else if let Some(alternative) = args.get(2) {
+        Ok(Tail::Eval((*alternative).clone(), env.clone()))
+    } else {
+        Ok(Tail::Value(Sexp::Atom(Atom::Nil)))
+    }
+}
+
+/// `(cond (test body...) ...)` evaluates body of the first clause with a true
+/// test. Clause without body yields value of its test, `(test => f)` calls
+/// `f` with that value and `else` clause matches unconditionally.
+fn cond(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    for clause in exp.into_iter() {
+        let (test, body) = match clause {
+            Sexp::Cons { car, cdr } => (&**car, &**cdr),
+            _ => {
+                return Err(Error::Reason(format!(
+                    "cond clause must be a list, got: {}",
+                    clause
+                )))
+            }
+        };
+
+        let value = match test {
+            Sexp::Atom(Atom::Symbol(s)) if original_name(s) == "else" => {
+                Sexp::Atom(Atom::Bool(true))
+            }
+            _ => evaluate(test, env)?,
+        };
+        if !value.is_truthy() {
+            continue;
+        }
+
+        return match body {
+            Sexp::Atom(Atom::Nil) => Ok(Tail::Value(value)),
+            Sexp::Cons { car, cdr } if is_arrow(car) => {
+                let receiver = match &**cdr {
+                    Sexp::Cons { car, cdr } if cdr.is_nil() => evaluate(car, env)?,
+                    _ => {
+                        return Err(Error::Reason(
+                            "cond => clause takes a single receiver".to_owned(),
+                        ))
+                    }
+                };
+                Ok(Tail::Call(receiver, Sexp::list(vec![value]), env.clone()))
+            }
+            _ => eval_body(body, env),
+        };
+    }
+
+    Ok(Tail::Value(Sexp::Atom(Atom::Nil)))
+}
+
+fn is_arrow(sexp: &Sexp) -> bool {
+    matches!(sexp, Sexp::Atom(Atom::Symbol(s)) if original_name(s) == "=>")
+}
+
+/// `(when test body...)` evaluates body when the test is true.
+fn when(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    conditional_body(exp, env, true)
+}
+
+/// `(unless test body...)` evaluates body when the test is false.
+fn unless(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    conditional_body(exp, env, false)
+}
+
+fn conditional_body(exp: &Sexp, env: &Env, expected: bool) -> Result<Tail, Error> {
+    if let Sexp::Cons { car, cdr } = exp {
+        if evaluate(car, env)?.is_truthy() == expected {
+            return eval_body(cdr, env);
+        }
+        return Ok(Tail::Value(Sexp::Atom(Atom::Nil)));
     }
 
-    Ok(Sexp::Cons {
-        car: Box::new(arg1.expect("missing argument after check").clone()),
-        cdr: Box::new(arg2.expect("missing argument after check").clone()),
-    })
+    Err(Error::Reason("missing test expression".to_owned()))
+}
+
+/// Evaluates arguments until one is false, the last argument is left in
+/// tail position.
+fn and(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    short_circuit(exp, env, false)
+}
+
+/// Evaluates arguments until one is true, the last argument is left in tail
+/// position.
+fn or(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    short_circuit(exp, env, true)
+}
+
+/// Stops at the first argument whose truthiness equals `until`. Without
+/// arguments evaluates to the opposite of `until`.
+fn short_circuit(exp: &Sexp, env: &Env, until: bool) -> Result<Tail, Error> {
+    let mut iter = exp.into_iter().peekable();
+    while let Some(item) = iter.next() {
+        if iter.peek().is_none() {
+            return Ok(Tail::Eval(item.clone(), env.clone()));
+        }
+
+        let result = evaluate(item, env)?;
+        if result.is_truthy() == until {
+            return Ok(Tail::Value(result));
+        }
+    }
+    Ok(Tail::Value(Sexp::Atom(Atom::Bool(!until))))
+}
+
+/// `(let ((name expr) ...) body...)` evaluates every `expr` in the enclosing
+/// environment and evaluates body in a child frame with the results bound.
+/// `(let loop ((name expr) ...) body...)` additionally binds `loop` to a
+/// function of the bound names with given body, so it can be called again.
+fn eval_let(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    let (name, bindings, body) = match exp {
+        Sexp::Cons { car, cdr } => match (&**car, &**cdr) {
+            (Sexp::Atom(Atom::Symbol(name)), Sexp::Cons { car, cdr }) => {
+                (Some(name), &**car, &**cdr)
+            }
+            _ => (None, &**car, &**cdr),
+        },
+        _ => return Err(Error::Reason("let takes bindings and a body".to_owned())),
+    };
+    let bindings = parse_bindings("let", bindings, true)?;
+    check_body("let", body)?;
+
+    let mut values = Vec::new();
+    for (_, init) in bindings.iter() {
+        values.push(evaluate(init, env)?);
+    }
+
+    if let Some(name) = name {
+        check_bindable(name)?;
+        let local = Env::new(Some(env.clone()));
+        let params = bindings
+            .iter()
+            .map(|(name, _)| Sexp::Atom(Atom::Symbol((*name).clone())))
+            .collect();
+        let lambda = Sexp::Atom(Atom::Lambda(new_lambda(&Sexp::list(params), body, &local)?));
+        local.insert(name.clone(), lambda.clone());
+        return Ok(Tail::Call(lambda, Sexp::list(values), env.clone()));
+    }
+
+    let local = Env::new(Some(env.clone()));
+    for ((name, _), value) in bindings.into_iter().zip(values) {
+        local.insert(name.clone(), value);
+    }
+    eval_body(body, &local)
+}
+
+/// `(let* ((name expr) ...) body...)` binds names one after another, so every
+/// `expr` sees bindings that precede it.
+fn let_star(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    let (bindings, body) = split_let("let*", exp)?;
+    let bindings = parse_bindings("let*", bindings, false)?;
+
+    let mut local = Env::new(Some(env.clone()));
+    for (name, init) in bindings {
+        let value = evaluate(init, &local)?;
+        local = Env::new(Some(local));
+        local.insert(name.clone(), value);
+    }
+    eval_body(body, &local)
+}
+
+/// `(letrec ((name expr) ...) body...)` evaluates every `expr` in a frame that
+/// already contains all the names, so bound functions can be mutually
+/// recursive. Names are assigned after all expressions are evaluated.
+fn letrec(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    let (bindings, body) = split_let("letrec", exp)?;
+    let bindings = parse_bindings("letrec", bindings, true)?;
+
+    let local = letrec_frame(&bindings, env);
+    let mut values = Vec::new();
+    for (_, init) in bindings.iter() {
+        values.push(evaluate(init, &local)?);
+    }
+    for ((name, _), value) in bindings.into_iter().zip(values) {
+        local.insert(name.clone(), value);
+    }
+    eval_body(body, &local)
+}
+
+/// Same as `letrec`, but every name is assigned right after its expression
+/// is evaluated, so later expressions can use values of earlier ones.
+fn letrec_star(exp: &Sexp, env: &Env) -> Result<Tail, Error> {
+    let (bindings, body) = split_let("letrec*", exp)?;
+    let bindings = parse_bindings("letrec*", bindings, true)?;
+
+    let local = letrec_frame(&bindings, env);
+    for (name, init) in bindings {
+        let value = evaluate(init, &local)?;
+        local.insert(name.clone(), value);
+    }
+    eval_body(body, &local)
+}
+
+fn letrec_frame(bindings: &[(&String, &Sexp)], env: &Env) -> Env {
+    let local = Env::new(Some(env.clone()));
+    for (name, _) in bindings {
+        local.insert((*name).clone(), Sexp::Atom(Atom::Nil));
+    }
+    local
+}
+
+fn split_let<'a>(form: &str, exp: &'a Sexp) -> Result<(&'a Sexp, &'a Sexp), Error> {
+    if let Sexp::Cons { car, cdr } = exp {
+        check_body(form, cdr)?;
+        return Ok((car, cdr));
+    }
+
+    Err(Error::Reason(format!("{} takes bindings and a body", form)))
+}
+
+fn check_body(form: &str, body: &Sexp) -> Result<(), Error> {
+    if let Sexp::Cons { .. } = body {
+        return Ok(());
+    }
+
+    Err(Error::Reason(format!("{} requires a body", form)))
+}
+
+/// Splits binding list of `form` into `(name, expr)` pairs.
+fn parse_bindings<'a>(
+    form: &str,
+    bindings: &'a Sexp,
+    unique: bool,
+) -> Result<Vec<(&'a String, &'a Sexp)>, Error> {
+    if let Sexp::Atom(atom) = bindings {
+        if !bindings.is_nil() {
+            return Err(Error::Reason(format!(
+                "{} bindings must be a list, got: {}",
+                form, atom
+            )));
+        }
+    }
+
+    let mut pairs: Vec<(&String, &Sexp)> = Vec::new();
+    for binding in bindings.into_iter() {
+        let pair = match binding {
+            Sexp::Cons { car, cdr } => match (&**car, &**cdr) {
+                (
+                    Sexp::Atom(Atom::Symbol(name)),
+                    Sexp::Cons {
+                        car: init,
+                        cdr: rest,
+                    },
+                ) if rest.is_nil() => Some((name, &**init)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let (name, init) = match pair {
+            Some(pair) => pair,
+            None => {
+                return Err(Error::Reason(format!(
+                    "malformed {} binding: {}",
+                    form, binding
+                )))
+            }
+        };
+        check_bindable(name)?;
+        if unique && pairs.iter().any(|(other, _)| *other == name) {
+            return Err(Error::Reason(format!(
+                "duplicate {} binding: {}",
+                form, name
+            )));
+        }
+        pairs.push((name, init));
+    }
+    Ok(pairs)
+}
+
+/// Parameter list is either a proper list of distinct symbols, a dotted list
+/// whose tail symbol collects remaining arguments, or a single symbol that
+/// collects all of them.
+fn check_params(params: &Sexp) -> Result<(), Error> {
+    let mut names: Vec<&str> = Vec::new();
+    let mut params = params;
+    loop {
+        let (name, rest) = match params {
+            Sexp::Atom(Atom::Nil) => return Ok(()),
+            Sexp::Atom(Atom::Symbol(rest)) => (rest, None),
+            Sexp::Cons { car, cdr } => match &**car {
+                Sexp::Atom(Atom::Symbol(name)) => (name, Some(&**cdr)),
+                _ => {
+                    return Err(Error::Reason(format!(
+                        "lambda parameter must be a symbol, got: {}",
+                        car
+                    )))
+                }
+            },
+            _ => {
+                return Err(Error::Reason(format!(
+                    "lambda parameter must be a symbol, got: {}",
+                    params
+                )))
+            }
+        };
+        check_bindable(name)?;
+        if names.contains(&name.as_str()) {
+            return Err(Error::Reason(format!(
+                "duplicate lambda parameter: {}",
+                name
+            )));
+        }
+        names.push(name);
+        match rest {
+            Some(rest) => params = rest,
+            None => return Ok(()),
+        }
+    }
 }
@@ -0,0 +1,298 @@
+use super::arity::{arguments, one_arg};
+use super::lists::{elements, index};
+use super::{Atom, Env, Error, Number, Sexp};
+use crate::eval::call;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+pub(super) fn text(sexp: &Sexp) -> Result<&Rc<str>, Error> {
+    match sexp {
+        Sexp::Atom(Atom::String(s)) => Ok(s),
+        _ => Err(Error::Reason(format!("expected string, got: {}", sexp))),
+    }
+}
+
+pub(super) fn string(s: impl Into<Rc<str>>) -> Sexp {
+    Sexp::Atom(Atom::String(s.into()))
+}
+
+fn boolean(b: bool) -> Sexp {
+    Sexp::Atom(Atom::Bool(b))
+}
+
+fn character(c: char) -> Sexp {
+    Sexp::Atom(Atom::Char(c))
+}
+
+fn integer(i: usize) -> Sexp {
+    Sexp::Atom(Atom::Number(Number::from(i as i64)))
+}
+
+/// Byte offset of character at `position` of `s`, which may be one past the
+/// last character. Strings are indexed by characters, that is Unicode scalar
+/// values, so no index can split a character.
+fn offset(s: &str, position: &Sexp) -> Result<usize, Error> {
+    let k = index(position)?;
+    s.char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(s.len()))
+        .nth(k)
+        .ok_or_else(|| {
+            Error::Reason(format!(
+                "index {} out of range for: {}",
+                position,
+                string(s)
+            ))
+        })
+}
+
+/// Character index of byte offset `i` of `s`.
+fn position(s: &str, i: usize) -> Sexp {
+    integer(s[..i].chars().count())
+}
+
+/// `(string->list s)` is list of characters of `s`.
+pub fn string_to_list(sexp: &Sexp) -> Result<Sexp, Error> {
+    let s = text(one_arg(sexp)?)?;
+    Ok(Sexp::list(s.chars().map(character).collect()))
+}
+
+/// `(list->string chars)` is string made of list of characters.
+pub fn list_to_string(sexp: &Sexp) -> Result<Sexp, Error> {
+    let list = one_arg(sexp)?;
+    let s = elements(list)?
+        .into_iter()
+        .map(|item| match item {
+            Sexp::Atom(Atom::Char(c)) => Ok(*c),
+            _ => Err(Error::Reason(format!("expected character, got: {}", item))),
+        })
+        .collect::<Result<String, Error>>()?;
+    Ok(string(s))
+}
+
+/// `(display x)` prints `x` to standard output, strings without quotes.
+pub fn display(sexp: &Sexp) -> Result<Sexp, Error> {
+    print!("{}", one_arg(sexp)?.display());
+    Ok(Sexp::Atom(Atom::Nil))
+}
+
+/// Number of characters, not bytes, in the string.
+pub fn string_length(sexp: &Sexp) -> Result<Sexp, Error> {
+    let s = text(one_arg(sexp)?)?;
+    Ok(integer(s.chars().count()))
+}
+
+/// `(string-ref s k)` is the character at position `k` of `s`.
+pub fn string_ref(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    let s = text(args[0])?;
+    let i = offset(s, args[1])?;
+    match s[i..].chars().next() {
+        Some(c) => Ok(character(c)),
+        None => Err(Error::Reason(format!(
+            "index {} out of range for: {}",
+            args[1], args[0]
+        ))),
+    }
+}
+
+/// `(substring s start [end])` is part of `s` from position `start` up to,
+/// but excluding, `end`, which defaults to the end of `s`.
+pub fn substring(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=3)?;
+    let s = text(args[0])?;
+    let start = offset(s, args[1])?;
+    let end = match args.get(2) {
+        Some(end) => offset(s, end)?,
+        None => s.len(),
+    };
+    if start > end {
+        return Err(Error::Reason(format!(
+            "substring start {} is after its end {}",
+            args[1], args[2]
+        )));
+    }
+    Ok(string(&s[start..end]))
+}
+
+pub fn string_append(sexp: &Sexp) -> Result<Sexp, Error> {
+    let mut result = String::new();
+    for arg in sexp.into_iter() {
+        result.push_str(text(arg)?);
+    }
+    Ok(string(result))
+}
+
+/// `(string-index s char-or-pred)` is position of the first character equal
+/// to the given one or satisfying the predicate, `false` if there is none.
+pub fn string_index(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    let s = text(args[0])?;
+    for (i, c) in s.chars().enumerate() {
+        let found = match args[1] {
+            Sexp::Atom(Atom::Char(wanted)) => c == *wanted,
+            pred => call(pred, vec![character(c)], env)?.is_truthy(),
+        };
+        if found {
+            return Ok(integer(i));
+        }
+    }
+    Ok(boolean(false))
+}
+
+/// `(string-contains s pattern)` is position of the first occurrence of
+/// `pattern` in `s`, `false` if there is none.
+pub fn string_contains(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    let (s, pattern) = (text(args[0])?, text(args[1])?);
+    Ok(match s.find(&**pattern) {
+        Some(i) => position(s, i),
+        None => boolean(false),
+    })
+}
+
+pub fn string_upcase(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(string(text(one_arg(sexp)?)?.to_uppercase()))
+}
+
+pub fn string_downcase(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(string(text(one_arg(sexp)?)?.to_lowercase()))
+}
+
+/// Case folded text for caseless comparison. Lowercasing the uppercase form
+/// character by character folds `ß` to `ss` and every sigma to `σ`, which
+/// `to_lowercase` alone doesn't.
+fn fold(s: &str) -> String {
+    s.chars()
+        .flat_map(char::to_uppercase)
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+pub fn string_foldcase(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(string(fold(text(one_arg(sexp)?)?)))
+}
+
+/// `(string-split s [separator])` splits `s` at every occurrence of string
+/// `separator`, or at runs of whitespace when it's omitted.
+pub fn string_split(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=2)?;
+    let s = text(args[0])?;
+    let parts: Vec<Sexp> = match args.get(1) {
+        None => s.split_whitespace().map(string).collect(),
+        Some(separator) => {
+            let separator = text(separator)?;
+            if separator.is_empty() {
+                return Err(Error::Reason("empty separator".to_owned()));
+            }
+            s.split(&**separator).map(string).collect()
+        }
+    };
+    Ok(Sexp::list(parts))
+}
+
+/// `(string-join strings [separator])` concatenates list of strings with
+/// `separator`, a single space by default, between them.
+pub fn string_join(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..=2)?;
+    let parts = elements(args[0])?
+        .into_iter()
+        .map(|part| text(part).map(|s| &**s))
+        .collect::<Result<Vec<&str>, Error>>()?;
+    let separator = match args.get(1) {
+        Some(separator) => text(separator)?,
+        None => " ",
+    };
+    Ok(string(parts.join(separator)))
+}
+
+pub fn string_trim(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(string(text(one_arg(sexp)?)?.trim()))
+}
+
+pub fn string_trim_left(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(string(text(one_arg(sexp)?)?.trim_start()))
+}
+
+pub fn string_trim_right(sexp: &Sexp) -> Result<Sexp, Error> {
+    Ok(string(text(one_arg(sexp)?)?.trim_end()))
+}
+
+/// `(string-prefix? prefix s)` tells whether `s` starts with `prefix`.
+pub fn string_prefix(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    Ok(boolean(text(args[1])?.starts_with(&**text(args[0])?)))
+}
+
+/// `(string-suffix? suffix s)` tells whether `s` ends with `suffix`.
+pub fn string_suffix(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 2..=2)?;
+    Ok(boolean(text(args[1])?.ends_with(&**text(args[0])?)))
+}
+
+/// `(string-replace s from to)` replaces every occurrence of `from` in `s`
+/// with `to`.
+pub fn string_replace(sexp: &Sexp) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 3..=3)?;
+    let (s, from, to) = (text(args[0])?, text(args[1])?, text(args[2])?);
+    if from.is_empty() {
+        return Err(Error::Reason("empty string to replace".to_owned()));
+    }
+    Ok(string(s.replace(&**from, to)))
+}
+
+/// Checks that `holds` is true for the ordering of every pair of adjacent
+/// arguments. Strings are ordered by characters, case folded first when
+/// `fold_case` is set.
+fn chain(sexp: &Sexp, fold_case: bool, holds: fn(Ordering) -> bool) -> Result<Sexp, Error> {
+    let args = arguments(sexp, 1..)?;
+    let mut strings = Vec::with_capacity(args.len());
+    for arg in args {
+        let s = text(arg)?;
+        strings.push(if fold_case { fold(s) } else { s.to_string() });
+    }
+    // UTF-8 preserves the order of scalar values, so comparing bytes orders
+    // by characters.
+    let result = strings.windows(2).all(|pair| holds(pair[0].cmp(&pair[1])));
+    Ok(boolean(result))
+}
+
+pub fn string_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, false, Ordering::is_eq)
+}
+
+pub fn string_less(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, false, Ordering::is_lt)
+}
+
+pub fn string_greater(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, false, Ordering::is_gt)
+}
+
+pub fn string_less_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, false, Ordering::is_le)
+}
+
+pub fn string_greater_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, false, Ordering::is_ge)
+}
+
+pub fn string_ci_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, Ordering::is_eq)
+}
+
+pub fn string_ci_less(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, Ordering::is_lt)
+}
+
+pub fn string_ci_greater(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, Ordering::is_gt)
+}
+
+pub fn string_ci_less_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, Ordering::is_le)
+}
+
+pub fn string_ci_greater_equal(sexp: &Sexp) -> Result<Sexp, Error> {
+    chain(sexp, true, Ordering::is_ge)
+}
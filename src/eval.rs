@@ -1,78 +1,388 @@
-use super::env::Env;
-use super::sexp::{Atom, Error, Sexp};
+use super::env::{special_form, Env};
+use super::sexp::{Atom, Error, Lambda, Sexp};
+use std::rc::Rc;
 
-pub fn eval(exp: &Sexp, env: &mut Env) -> Result<Sexp, Error> {
+/// Outcome of a single evaluation step.
+///
+/// Special forms and function calls hand expressions in tail position back to
+/// the `eval` loop instead of evaluating them recursively, so tail calls run
+/// in constant Rust stack.
+pub enum Tail {
+    Value(Sexp),
+    Eval(Sexp, Env),
+    Call(Sexp, Sexp, Env),
+}
+
+/// Expands every macro call in `exp` and evaluates the result, so macros used
+/// in loops and function bodies are expanded once rather than on every pass.
+pub fn eval(exp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    evaluate(&expand_macros(exp, env, &[])?, env)
+}
+
+/// Evaluates `exp` without expanding macros first, calls to macros that were
+/// defined after the expansion pass are expanded when they are reached.
+pub(crate) fn evaluate(exp: &Sexp, env: &Env) -> Result<Sexp, Error> {
     match exp {
         Sexp::Atom(a) => eval_atom(a, env),
-        Sexp::Cons { car, cdr } => eval_cons(car, cdr, env),
+        Sexp::Cons { car, cdr } => trampoline(eval_cons(car, cdr, env)?),
+    }
+}
+
+fn trampoline(mut next: Tail) -> Result<Sexp, Error> {
+    loop {
+        next = match next {
+            Tail::Value(value) => return Ok(value),
+            Tail::Eval(exp, env) => match &exp {
+                Sexp::Atom(a) => return eval_atom(a, &env),
+                Sexp::Cons { car, cdr } => eval_cons(car, cdr, &env)?,
+            },
+            Tail::Call(func, args, env) => apply(&func, &args, &env)?,
+        }
     }
 }
 
-fn eval_atom(atom: &Atom, env: &mut Env) -> Result<Sexp, Error> {
+fn eval_atom(atom: &Atom, env: &Env) -> Result<Sexp, Error> {
     match atom {
         Atom::Nil => Ok(Sexp::Atom(Atom::Nil)),
         c @ Atom::Char(_) => Ok(Sexp::Atom(c.clone())),
         b @ Atom::Bool(_) => Ok(Sexp::Atom(b.clone())),
-        Atom::Symbol(s) => env.search(&s),
+        s @ Atom::String(_) => Ok(Sexp::Atom(s.clone())),
+        Atom::Symbol(s) if special_form(s).is_some() => Err(Error::Reason(format!(
+            "special form can't be used as a value: {}",
+            s
+        ))),
+        Atom::Symbol(s) => env.search(s),
         n @ Atom::Number(_) => Ok(Sexp::Atom(n.clone())),
         f @ Atom::Func { .. } => Ok(Sexp::Atom(f.clone())),
+        f @ Atom::EnvFunc { .. } => Ok(Sexp::Atom(f.clone())),
+        l @ Atom::Lambda(_) => Ok(Sexp::Atom(l.clone())),
+        m @ Atom::Macro(_) => Ok(Sexp::Atom(m.clone())),
+        m @ Atom::Syntax(_) => Ok(Sexp::Atom(m.clone())),
     }
 }
 
-fn eval_cons(car: &Sexp, cdr: &Sexp, env: &mut Env) -> Result<Sexp, Error> {
-    match car {
-        Sexp::Atom(a) => apply(a, cdr, env),
-        cons @ Sexp::Cons { .. } => {
-            let func = eval(cons, env)?;
-            eval_cons(&func, cdr, env)
+fn eval_cons(car: &Sexp, cdr: &Sexp, env: &Env) -> Result<Tail, Error> {
+    if let Sexp::Atom(Atom::Symbol(s)) = car {
+        if let Some(form) = special_form(s) {
+            return form(cdr, env);
         }
     }
+
+    let func = evaluate(car, env)?;
+    match &func {
+        Sexp::Atom(Atom::Macro(transformer)) => {
+            let expansion = expand(transformer, cdr)?;
+            return Ok(Tail::Eval(
+                expand_macros(&expansion, env, &[])?,
+                env.clone(),
+            ));
+        }
+        Sexp::Atom(Atom::Syntax(rules)) => {
+            let expansion = rules.expand(cdr)?;
+            return Ok(Tail::Eval(
+                expand_macros(&expansion, env, &[])?,
+                env.clone(),
+            ));
+        }
+        _ => {}
+    }
+
+    let args = map_eval(cdr, env)?;
+    Ok(Tail::Call(func, args, env.clone()))
 }
 
-fn map_eval(sexp: &Sexp, env: &mut Env) -> Result<Sexp, Error> {
-    let mut vec: Vec<Sexp> = Vec::new();
-    for item in sexp.into_iter() {
-        let res = eval(item, env)?;
-        vec.push(res)
+/// Expands `exp` once when it's a call to a macro bound in `env`, returns
+/// `None` for any other expression.
+pub fn macroexpand_1(exp: &Sexp, env: &Env) -> Result<Option<Sexp>, Error> {
+    if let Sexp::Cons { car, cdr } = exp {
+        if let Sexp::Atom(Atom::Symbol(s)) = &**car {
+            if special_form(s).is_none() {
+                match &env.search(s) {
+                    Ok(Sexp::Atom(Atom::Macro(transformer))) => {
+                        return Ok(Some(expand(transformer, cdr)?))
+                    }
+                    Ok(Sexp::Atom(Atom::Syntax(rules))) => return Ok(Some(rules.expand(cdr)?)),
+                    _ => {}
+                }
+            }
+        }
     }
+    Ok(None)
+}
 
-    let mut rev = vec.iter().rev();
-    if let Some(first) = rev.next() {
-        let mut sexp = Box::new(Sexp::Cons {
-            car: Box::new(first.clone()),
-            cdr: Box::new(Sexp::Atom(Atom::Nil)),
-        });
+/// Expands `exp` until it's no longer a call to a macro.
+pub fn macroexpand(exp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let mut exp = exp.clone();
+    while let Some(expansion) = macroexpand_1(&exp, env)? {
+        exp = expansion;
+    }
+    Ok(exp)
+}
 
-        for item in rev {
-            let new = Box::new(Sexp::Cons {
-                car: Box::new(item.clone()),
-                cdr: sexp,
-            });
+/// Expansion pass, expands macro calls in `exp` and in all of its
+/// subexpressions. Names in `bound` are bound by forms enclosing `exp` and
+/// shadow macros of the same name. Quoted data and `syntax-rules` specs are
+/// left untouched.
+fn expand_macros(exp: &Sexp, env: &Env, bound: &[String]) -> Result<Sexp, Error> {
+    let (car, cdr) = match exp {
+        Sexp::Cons { car, cdr } => (&**car, &**cdr),
+        Sexp::Atom(_) => return Ok(exp.clone()),
+    };
+    if let Sexp::Atom(Atom::Symbol(s)) = car {
+        if !bound.contains(s) {
+            if special_form(s).is_some() {
+                return expand_special_form(s, exp, cdr, env, bound);
+            }
+            match &env.search(s) {
+                Ok(Sexp::Atom(Atom::Macro(transformer))) => {
+                    return expand_macros(&expand(transformer, cdr)?, env, bound)
+                }
+                Ok(Sexp::Atom(Atom::Syntax(rules))) => {
+                    return expand_macros(&rules.expand(cdr)?, env, bound)
+                }
+                _ => {}
+            }
+        }
+    }
+    expand_each(exp, env, bound)
+}
 
-            sexp = new;
+/// Expands arguments `args` of special form `form` with respect to the names
+/// it binds. Malformed forms are left for the special form to report.
+fn expand_special_form(
+    form: &str,
+    exp: &Sexp,
+    args: &Sexp,
+    env: &Env,
+    bound: &[String],
+) -> Result<Sexp, Error> {
+    let keyword = Sexp::Atom(Atom::Symbol(form.to_owned()));
+    let (first, rest) = match args {
+        Sexp::Cons { car, cdr } => (&**car, &**cdr),
+        Sexp::Atom(_) => return Ok(exp.clone()),
+    };
+    let expanded = match form {
+        "quote" | "quasiquote" | "unquote" | "unquote-splicing" | "define-syntax"
+        | "syntax-rules" => return Ok(exp.clone()),
+        "lambda" => cons(
+            first.clone(),
+            expand_each(rest, env, &with_names(bound, params(first)))?,
+        ),
+        "defmacro" => match rest {
+            Sexp::Cons { car, cdr } => cons(
+                first.clone(),
+                cons(
+                    (**car).clone(),
+                    expand_each(cdr, env, &with_names(bound, params(car)))?,
+                ),
+            ),
+            Sexp::Atom(_) => return Ok(exp.clone()),
+        },
+        "define" => match first {
+            Sexp::Cons { car, cdr } => {
+                let mut names = params(cdr);
+                names.extend(params(car));
+                cons(
+                    first.clone(),
+                    expand_each(rest, env, &with_names(bound, names))?,
+                )
+            }
+            Sexp::Atom(_) => cons(first.clone(), expand_each(rest, env, bound)?),
+        },
+        "let" => match (first, rest) {
+            (Sexp::Atom(Atom::Symbol(name)), Sexp::Cons { car, cdr }) => {
+                let mut names = binding_names(car);
+                names.push(name.clone());
+                cons(
+                    first.clone(),
+                    cons(
+                        expand_bindings(car, env, bound, false)?,
+                        expand_each(cdr, env, &with_names(bound, names))?,
+                    ),
+                )
+            }
+            _ => cons(
+                expand_bindings(first, env, bound, false)?,
+                expand_each(rest, env, &with_names(bound, binding_names(first)))?,
+            ),
+        },
+        "let*" => cons(
+            expand_bindings(first, env, bound, true)?,
+            expand_each(rest, env, &with_names(bound, binding_names(first)))?,
+        ),
+        "letrec" | "letrec*" => {
+            let inner = with_names(bound, binding_names(first));
+            cons(
+                expand_bindings(first, env, &inner, false)?,
+                expand_each(rest, env, &inner)?,
+            )
+        }
+        // Clauses aren't calls, only the expressions inside them are.
+        "cond" => {
+            let mut clauses = Vec::new();
+            for clause in args.into_iter() {
+                clauses.push(expand_each(clause, env, bound)?);
+            }
+            return Ok(cons(keyword, Sexp::list(clauses)));
         }
+        _ => return Ok(cons(keyword, expand_each(args, env, bound)?)),
+    };
+    Ok(cons(keyword, expanded))
+}
 
-        return Ok(*sexp);
+/// Expands every element of list `exp`, an improper tail is left as is.
+fn expand_each(exp: &Sexp, env: &Env, bound: &[String]) -> Result<Sexp, Error> {
+    let mut items = Vec::new();
+    let mut rest = exp;
+    while let Sexp::Cons { car, cdr } = rest {
+        items.push(expand_macros(car, env, bound)?);
+        rest = cdr;
     }
+    Ok(items.into_iter().rfold(rest.clone(), |acc, x| cons(x, acc)))
+}
 
-    Err(Error::Reason("missing argument list".to_owned()))
+/// Expands initial values of `((name expr) ...)` bindings. When `sequential`
+/// is set every `expr` is in scope of the names bound before it, as in
+/// `let*`.
+fn expand_bindings(
+    bindings: &Sexp,
+    env: &Env,
+    bound: &[String],
+    sequential: bool,
+) -> Result<Sexp, Error> {
+    let mut scope = bound.to_vec();
+    let mut expanded = Vec::new();
+    for item in bindings.into_iter() {
+        match item {
+            Sexp::Cons { car, cdr } => {
+                expanded.push(cons((**car).clone(), expand_each(cdr, env, &scope)?));
+                if let (true, Sexp::Atom(Atom::Symbol(name))) = (sequential, &**car) {
+                    scope.push(name.clone());
+                }
+            }
+            _ => return Ok(bindings.clone()),
+        }
+    }
+    Ok(Sexp::list(expanded))
 }
 
-fn apply(func: &Atom, args: &Sexp, env: &mut Env) -> Result<Sexp, Error> {
-    if let Atom::Func { fun, name } = func {
-        if name == &"'" {
-            return fun(args);
+fn binding_names(bindings: &Sexp) -> Vec<String> {
+    bindings
+        .into_iter()
+        .filter_map(|item| match item {
+            Sexp::Cons { car, .. } => match &**car {
+                Sexp::Atom(Atom::Symbol(name)) => Some(name.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Names bound by parameter list `params`, including a rest parameter.
+fn params(list: &Sexp) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = list;
+    while let Sexp::Cons { car, cdr } = rest {
+        if let Sexp::Atom(Atom::Symbol(name)) = &**car {
+            names.push(name.clone());
+        }
+        rest = cdr;
+    }
+    if let Sexp::Atom(Atom::Symbol(name)) = rest {
+        names.push(name.clone());
+    }
+    names
+}
+
+fn with_names(bound: &[String], names: Vec<String>) -> Vec<String> {
+    let mut scope = bound.to_vec();
+    scope.extend(names);
+    scope
+}
+
+fn cons(car: Sexp, cdr: Sexp) -> Sexp {
+    Sexp::Cons {
+        car: Rc::new(car),
+        cdr: Rc::new(cdr),
+    }
+}
+
+fn expand(transformer: &Lambda, args: &Sexp) -> Result<Sexp, Error> {
+    let local = Env::new(Some(transformer.env.clone()));
+    bind_params(&transformer.params_exp, args, &local)?;
+    trampoline(eval_body(&transformer.body_exp, &local)?)
+}
+
+fn map_eval(sexp: &Sexp, env: &Env) -> Result<Sexp, Error> {
+    let mut vec: Vec<Sexp> = Vec::new();
+    for item in sexp.into_iter() {
+        let res = evaluate(item, env)?;
+        vec.push(res)
+    }
+
+    Ok(Sexp::list(vec))
+}
+
+/// Evaluates all but the last expression of `body`, the last one is left
+/// in tail position.
+pub(crate) fn eval_body(body: &Sexp, env: &Env) -> Result<Tail, Error> {
+    let mut iter = body.into_iter().peekable();
+    while let Some(exp) = iter.next() {
+        if iter.peek().is_none() {
+            return Ok(Tail::Eval(exp.clone(), env.clone()));
         }
-        let args = map_eval(args, env)?;
-        return fun(&args);
+        evaluate(exp, env)?;
     }
+    Ok(Tail::Value(Sexp::Atom(Atom::Nil)))
+}
 
-    if let Atom::Symbol(s) = func {
-        let fun = env.search(s)?;
-        return eval_cons(&fun, args, env);
+fn bind_params(params: &Sexp, args: &Sexp, env: &Env) -> Result<(), Error> {
+    match (params, args) {
+        (Sexp::Atom(Atom::Nil), Sexp::Atom(Atom::Nil)) => Ok(()),
+        (Sexp::Atom(Atom::Symbol(rest)), _) => {
+            env.insert(rest.clone(), args.clone());
+            Ok(())
+        }
+        (
+            Sexp::Cons {
+                car: param,
+                cdr: params,
+            },
+            Sexp::Cons {
+                car: arg,
+                cdr: args,
+            },
+        ) => {
+            if let Sexp::Atom(Atom::Symbol(name)) = &**param {
+                env.insert(name.clone(), (**arg).clone());
+            }
+            bind_params(params, args, env)
+        }
+        (Sexp::Cons { .. }, _) => Err(Error::Reason(
+            "too few arguments passed to lambda".to_owned(),
+        )),
+        _ => Err(Error::Reason(
+            "too many arguments passed to lambda".to_owned(),
+        )),
     }
+}
 
-    Err(Error::Reason(
-        "first argument must be a function".to_owned(),
-    ))
+/// Calls `func` with already evaluated `args` and runs it to completion. This
+/// is how builtins call back into procedures they were passed.
+pub fn call(func: &Sexp, args: Vec<Sexp>, env: &Env) -> Result<Sexp, Error> {
+    trampoline(apply(func, &Sexp::list(args), env)?)
+}
+
+fn apply(func: &Sexp, args: &Sexp, env: &Env) -> Result<Tail, Error> {
+    match func {
+        Sexp::Atom(Atom::Func { fun, .. }) => Ok(Tail::Value(fun(args)?)),
+        Sexp::Atom(Atom::EnvFunc { fun, .. }) => Ok(Tail::Value(fun(args, env)?)),
+        Sexp::Atom(Atom::Lambda(lambda)) => {
+            let local = Env::new(Some(lambda.env.clone()));
+            bind_params(&lambda.params_exp, args, &local)?;
+            eval_body(&lambda.body_exp, &local)
+        }
+        _ => Err(Error::Reason(format!("not a function: {}", func))),
+    }
 }
@@ -8,6 +8,9 @@ pub enum Token {
     Symbol(String),
     String(String),
     Quote,
+    Quasiquote,
+    Unquote,
+    UnquoteSplicing,
     LParen,
     RParen,
 }
@@ -19,7 +22,7 @@ pub enum LexerError {
 
 impl fmt::Display for LexerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match &*self {
+        match self {
             LexerError::Reason(res) => write!(f, "Lexer error: {}", res),
         }
     }
@@ -42,12 +45,25 @@ pub fn tokenize(code: String) -> Result<VecDeque<Token>, LexerError> {
             tokens.push_back(Token::Quote);
             chars.next();
             continue;
+        } else if c == &'`' {
+            tokens.push_back(Token::Quasiquote);
+            chars.next();
+            continue;
+        } else if c == &',' {
+            chars.next();
+            if chars.peek() == Some(&'@') {
+                tokens.push_back(Token::UnquoteSplicing);
+                chars.next();
+            } else {
+                tokens.push_back(Token::Unquote);
+            }
+            continue;
         } else if c == &'\"' {
             chars.next();
             let token = tokenize_string(&mut chars)?;
             tokens.push_back(token);
             continue;
-        } else if (&c).is_whitespace() {
+        } else if c.is_whitespace() {
             chars.next();
             continue;
         }
@@ -61,10 +77,7 @@ pub fn tokenize(code: String) -> Result<VecDeque<Token>, LexerError> {
 
 fn tokenize_symbol(code: &mut Peekable<Chars>) -> Result<Token, LexerError> {
     fn is_forbidden(c: char) -> bool {
-        match c {
-            '\"' | '\'' => true,
-            _ => false,
-        }
+        matches!(c, '\"' | '\'' | '`' | ',')
     }
 
     fn is_terminal(c: char) -> bool {
@@ -100,14 +113,88 @@ fn tokenize_symbol(code: &mut Peekable<Chars>) -> Result<Token, LexerError> {
     Ok(Token::Symbol(symbol))
 }
 
+/// Reads string literal after its opening quote. Backslash starts an escape:
+/// `\"`, `\\`, `\n`, `\t`, `\r`, `\0`, `\xHH;` and `\u{HHHH}` stand for single
+/// characters, and backslash at the end of a line continues the string on the
+/// next one, skipping whitespace around the line break.
 fn tokenize_string(code: &mut Peekable<Chars>) -> Result<Token, LexerError> {
     let mut string = String::new();
     while let Some(c) = code.next() {
-        if c == '\"' {
-            return Ok(Token::String(string));
+        match c {
+            '\"' => return Ok(Token::String(string)),
+            '\\' => tokenize_escape(code, &mut string).map_err(|escape| {
+                LexerError::Reason(format!(
+                    "invalid escape {} after {:?} in string",
+                    escape, string
+                ))
+            })?,
+            c => string.push(c),
         }
-        string.push(c)
     }
 
     Err(LexerError::Reason("missing closing quote".to_owned()))
 }
+
+/// Reads escape after a backslash into `string`, failing with the text of
+/// the invalid escape.
+fn tokenize_escape(code: &mut Peekable<Chars>, string: &mut String) -> Result<(), String> {
+    let c = match code.next() {
+        Some(c) => c,
+        None => return Err("\\ at end of input".to_owned()),
+    };
+    match c {
+        '"' | '\\' => string.push(c),
+        'n' => string.push('\n'),
+        't' => string.push('\t'),
+        'r' => string.push('\r'),
+        '0' => string.push('\0'),
+        'x' => {
+            let digits = take_until(code, ';').map_err(|digits| format!("\\x{}", digits))?;
+            string.push(scalar(&digits).ok_or_else(|| format!("\\x{};", digits))?);
+        }
+        'u' => {
+            if code.next_if_eq(&'{').is_none() {
+                return Err("\\u".to_owned());
+            }
+            let digits = take_until(code, '}').map_err(|digits| format!("\\u{{{}", digits))?;
+            string.push(scalar(&digits).ok_or_else(|| format!("\\u{{{}}}", digits))?);
+        }
+        c if c.is_whitespace() => {
+            // Line continuation, only whitespace may separate the backslash
+            // from the line break.
+            let mut c = c;
+            while c != '\n' {
+                match code.next() {
+                    Some(next) if next.is_whitespace() => c = next,
+                    _ => return Err("\\ followed by whitespace".to_owned()),
+                }
+            }
+            while code.next_if(|c| *c != '\n' && c.is_whitespace()).is_some() {}
+        }
+        c => return Err(format!("\\{}", c)),
+    }
+    Ok(())
+}
+
+/// Collects characters up to and including `end`, failing with the collected
+/// text when input ends or a quote comes first.
+fn take_until(code: &mut Peekable<Chars>, end: char) -> Result<String, String> {
+    let mut text = String::new();
+    while let Some(c) = code.next_if(|c| *c != '"') {
+        if c == end {
+            return Ok(text);
+        }
+        text.push(c);
+    }
+    Err(text)
+}
+
+/// Unicode scalar value written as 1 to 6 hex digits.
+fn scalar(digits: &str) -> Option<char> {
+    if digits.is_empty() || digits.len() > 6 {
+        return None;
+    }
+    u32::from_str_radix(digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+}
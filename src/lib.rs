@@ -4,3 +4,4 @@ pub mod lexer;
 pub mod number;
 pub mod parser;
 pub mod sexp;
+pub mod syntax_rules;
@@ -30,11 +30,11 @@ fn main() {
 }
 
 fn simple_eval(code: String) {
-    let mut default_env = Env::default_env();
+    let default_env = Env::default_env();
     let tokens = tokenize(code);
     if let Ok(mut deque) = tokens {
         let res = read_from_tokens(&mut deque).unwrap();
-        match eval(&res, &mut default_env) {
+        match eval(&res, &default_env) {
             Ok(sexp) => println!("{}", sexp),
             Err(err) => println!("{}", err),
         }
@@ -0,0 +1,578 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Arbitrary precision integer stored as sign and magnitude.
+///
+/// Magnitude holds base 2^32 digits, least significant first, without
+/// leading zeros. Zero has empty magnitude and is never negative, so every
+/// value has exactly one representation and derived equality is correct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt {
+            negative: false,
+            magnitude: Vec::new(),
+        }
+    }
+
+    fn from_parts(negative: bool, mut magnitude: Vec<u32>) -> BigInt {
+        trim(&mut magnitude);
+        BigInt {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.magnitude.first().is_none_or(|digit| digit % 2 == 0)
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.magnitude.len() > 2 {
+            return None;
+        }
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0u64, |acc, digit| (acc << 32) | u64::from(*digit));
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    pub fn to_i32(&self) -> Option<i32> {
+        self.to_i64().and_then(|i| i32::try_from(i).ok())
+    }
+
+    /// Nearest float, infinite when the value is out of range of `f64`.
+    pub fn to_f64(&self) -> f64 {
+        // Parsing the decimal form gives correctly rounded result.
+        self.to_string()
+            .parse()
+            .expect("decimal integer is a valid float")
+    }
+
+    /// Exact value of integral float, `None` for fractions, infinities and
+    /// NaN.
+    pub fn from_f64(f: f64) -> Option<BigInt> {
+        if !f.is_finite() || f.fract() != 0.0 {
+            return None;
+        }
+        let bits = f.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i64;
+        let mantissa = if exponent == 0 {
+            (bits & ((1 << 52) - 1)) << 1
+        } else {
+            (bits & ((1 << 52) - 1)) | (1 << 52)
+        };
+        // Value is `mantissa * 2^(exponent - 1075)`.
+        let shift = exponent - 1075;
+        let magnitude = if shift >= 0 {
+            shl_bits(&split_u64(mantissa), shift as usize)
+        } else {
+            split_u64(mantissa >> (-shift).min(63))
+        };
+        Some(BigInt::from_parts(f < 0.0, magnitude))
+    }
+
+    pub fn neg(&self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_parts(false, self.magnitude.clone())
+    }
+
+    pub fn add(&self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(
+                self.negative,
+                add_magnitudes(&self.magnitude, &other.magnitude),
+            );
+        }
+        match cmp_magnitudes(&self.magnitude, &other.magnitude) {
+            Ordering::Less => BigInt::from_parts(
+                other.negative,
+                sub_magnitudes(&other.magnitude, &self.magnitude),
+            ),
+            _ => BigInt::from_parts(
+                self.negative,
+                sub_magnitudes(&self.magnitude, &other.magnitude),
+            ),
+        }
+    }
+
+    pub fn sub(&self, other: &BigInt) -> BigInt {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != other.negative,
+            mul_magnitudes(&self.magnitude, &other.magnitude),
+        )
+    }
+
+    /// Multiplies by `2^bits`.
+    pub fn shl(&self, bits: usize) -> BigInt {
+        BigInt::from_parts(self.negative, shl_bits(&self.magnitude, bits))
+    }
+
+    /// Divides by `2^bits`, rounding towards negative infinity.
+    pub fn shr(&self, bits: usize) -> BigInt {
+        let (digits, bits) = (bits / 32, bits % 32);
+        if digits >= self.magnitude.len() {
+            return BigInt::from(if self.negative { -1 } else { 0 });
+        }
+        let dropped = self.magnitude[..digits].iter().any(|digit| *digit != 0)
+            || self.magnitude[digits] & ((1 << bits) - 1) != 0;
+        let shifted = BigInt::from_parts(
+            self.negative,
+            shr_small(&self.magnitude[digits..], bits as u32),
+        );
+        if self.negative && dropped {
+            return shifted.sub(&BigInt::from(1));
+        }
+        shifted
+    }
+
+    pub fn and(&self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a & b)
+    }
+
+    pub fn or(&self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a | b)
+    }
+
+    pub fn xor(&self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a ^ b)
+    }
+
+    /// Bitwise complement, which in two's complement is `-n - 1`.
+    pub fn not(&self) -> BigInt {
+        self.neg().sub(&BigInt::from(1))
+    }
+
+    /// Applies `op` digit by digit to two's complement representations,
+    /// which are one digit longer than either magnitude so the top digit only
+    /// holds sign bits.
+    fn bitwise(&self, other: &BigInt, op: fn(u32, u32) -> u32) -> BigInt {
+        let len = self.magnitude.len().max(other.magnitude.len()) + 1;
+        let digits = self
+            .twos_complement(len)
+            .iter()
+            .zip(other.twos_complement(len).iter())
+            .map(|(a, b)| op(*a, *b))
+            .collect();
+        BigInt::from_twos_complement(digits)
+    }
+
+    fn twos_complement(&self, len: usize) -> Vec<u32> {
+        let mut digits = self.magnitude.clone();
+        digits.resize(len, 0);
+        if self.negative {
+            negate_digits(&mut digits);
+        }
+        digits
+    }
+
+    fn from_twos_complement(mut digits: Vec<u32>) -> BigInt {
+        let negative = digits.last().is_some_and(|top| top >> 31 == 1);
+        if negative {
+            negate_digits(&mut digits);
+        }
+        BigInt::from_parts(negative, digits)
+    }
+
+    /// Number of one bits of non-negative number, or of zero bits of the two's
+    /// complement of negative one.
+    pub fn count_ones(&self) -> u64 {
+        let n = if self.negative {
+            self.not()
+        } else {
+            self.clone()
+        };
+        n.magnitude
+            .iter()
+            .map(|digit| u64::from(digit.count_ones()))
+            .sum()
+    }
+
+    /// Number of bits of the magnitude, zero for zero.
+    pub fn bit_length(&self) -> usize {
+        match self.magnitude.last() {
+            Some(top) => self.magnitude.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Raises to the power of `exponent` by repeated squaring.
+    pub fn pow(&self, mut exponent: u64) -> BigInt {
+        let mut base = self.clone();
+        let mut result = BigInt::from(1);
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.mul(&base);
+            }
+            exponent /= 2;
+            if exponent > 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+
+    /// Largest integer whose square doesn't exceed the magnitude.
+    pub fn isqrt(&self) -> BigInt {
+        if self.is_zero() {
+            return BigInt::zero();
+        }
+        let n = self.abs();
+        // Newton's iteration decreases monotonically from any estimate above
+        // the root, and stops once it would increase again.
+        let mut root = BigInt::from(1).shl(n.bit_length().div_ceil(2));
+        loop {
+            let (quotient, _) = n.div_rem(&root).expect("root is not zero");
+            let (next, _) = root
+                .add(&quotient)
+                .div_rem(&BigInt::from(2))
+                .expect("two is not zero");
+            if next >= root {
+                return root;
+            }
+            root = next;
+        }
+    }
+
+    /// Greatest common divisor, it is never negative.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let (_, remainder) = a.div_rem(&b).expect("divisor is not zero");
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    /// Quotient truncated towards zero and remainder with the sign of the
+    /// dividend, `None` when dividing by zero.
+    pub fn div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitudes(&self.magnitude, &other.magnitude);
+        Some((
+            BigInt::from_parts(self.negative != other.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        ))
+    }
+
+    /// Parses optionally signed digits in `radix`, which must be between 2
+    /// and 36.
+    pub fn parse(s: &str, radix: u32) -> Option<BigInt> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() {
+            return None;
+        }
+
+        let mut magnitude = Vec::new();
+        for c in digits.chars() {
+            let digit = c.to_digit(radix)?;
+            mul_add_small(&mut magnitude, radix, digit);
+        }
+        Some(BigInt::from_parts(negative, magnitude))
+    }
+
+    /// Formats the value in `radix`, which must be between 2 and 36, using
+    /// lowercase letters for digits above 9.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        if self.is_zero() {
+            return "0".to_owned();
+        }
+
+        // Splits off as many digits at once as fit in `u32`.
+        let mut chunk = radix;
+        let mut chunk_digits = 1;
+        while let Some(next) = chunk.checked_mul(radix) {
+            chunk = next;
+            chunk_digits += 1;
+        }
+
+        let mut magnitude = self.magnitude.clone();
+        let mut digits = Vec::new();
+        while !magnitude.is_empty() {
+            let mut rest = div_rem_small(&mut magnitude, chunk);
+            for _ in 0..chunk_digits {
+                if magnitude.is_empty() && rest == 0 {
+                    break;
+                }
+                digits.push(std::char::from_digit(rest % radix, radix).expect("digit in radix"));
+                rest /= radix;
+            }
+        }
+        if self.negative {
+            digits.push('-');
+        }
+        digits.iter().rev().collect()
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(i: i64) -> BigInt {
+        BigInt::from_parts(i < 0, split_u64(i.unsigned_abs()))
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(i: i32) -> BigInt {
+        BigInt::from(i64::from(i))
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitudes(&self.magnitude, &other.magnitude),
+            (true, true) => cmp_magnitudes(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str_radix(10))
+    }
+}
+
+fn trim(magnitude: &mut Vec<u32>) {
+    while magnitude.last() == Some(&0) {
+        magnitude.pop();
+    }
+}
+
+/// Negates fixed width two's complement number in place.
+fn negate_digits(digits: &mut [u32]) {
+    let mut carry = true;
+    for digit in digits.iter_mut() {
+        let (sum, overflow) = (!*digit).overflowing_add(u32::from(carry));
+        *digit = sum;
+        carry = overflow;
+    }
+}
+
+fn split_u64(n: u64) -> Vec<u32> {
+    let mut magnitude = vec![n as u32, (n >> 32) as u32];
+    trim(&mut magnitude);
+    magnitude
+}
+
+fn cmp_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, digit) in long.iter().enumerate() {
+        let total = u64::from(*digit) + u64::from(*short.get(i).unwrap_or(&0)) + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    if carry > 0 {
+        sum.push(carry as u32);
+    }
+    sum
+}
+
+/// Subtracts `b` from `a`, which must not be smaller.
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, digit) in a.iter().enumerate() {
+        let mut total = i64::from(*digit) - i64::from(*b.get(i).unwrap_or(&0)) - borrow;
+        borrow = 0;
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(total as u32);
+    }
+    trim(&mut difference);
+    difference
+}
+
+fn mul_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, y) in b.iter().enumerate() {
+            let total = u64::from(*x) * u64::from(*y) + u64::from(product[i + j]) + carry;
+            product[i + j] = total as u32;
+            carry = total >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    trim(&mut product);
+    product
+}
+
+/// Computes `magnitude * factor + addend` in place.
+fn mul_add_small(magnitude: &mut Vec<u32>, factor: u32, addend: u32) {
+    let mut carry = u64::from(addend);
+    for digit in magnitude.iter_mut() {
+        let total = u64::from(*digit) * u64::from(factor) + carry;
+        *digit = total as u32;
+        carry = total >> 32;
+    }
+    if carry > 0 {
+        magnitude.push(carry as u32);
+    }
+}
+
+/// Divides `magnitude` by non-zero `divisor` in place and returns the
+/// remainder.
+fn div_rem_small(magnitude: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut remainder = 0u64;
+    for digit in magnitude.iter_mut().rev() {
+        let current = (remainder << 32) | u64::from(*digit);
+        *digit = (current / u64::from(divisor)) as u32;
+        remainder = current % u64::from(divisor);
+    }
+    trim(magnitude);
+    remainder as u32
+}
+
+/// Shifts `magnitude` left by `bits`.
+fn shl_bits(magnitude: &[u32], bits: usize) -> Vec<u32> {
+    let (digits, bits) = (bits / 32, bits % 32);
+    let mut shifted = vec![0u32; digits];
+    let mut carry = 0u32;
+    for digit in magnitude {
+        shifted.push((digit << bits) | carry);
+        carry = if bits == 0 { 0 } else { digit >> (32 - bits) };
+    }
+    shifted.push(carry);
+    trim(&mut shifted);
+    shifted
+}
+
+/// Shifts `magnitude` right by less than 32 `bits`.
+fn shr_small(magnitude: &[u32], bits: u32) -> Vec<u32> {
+    if bits == 0 {
+        return magnitude.to_vec();
+    }
+    let mut shifted: Vec<u32> = magnitude
+        .iter()
+        .enumerate()
+        .map(|(i, digit)| {
+            let high = magnitude.get(i + 1).map_or(0, |next| next << (32 - bits));
+            (digit >> bits) | high
+        })
+        .collect();
+    trim(&mut shifted);
+    shifted
+}
+
+/// Long division of magnitudes, algorithm D from section 4.3.1 of The Art of
+/// Computer Programming.
+fn div_rem_magnitudes(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_magnitudes(u, v) == Ordering::Less {
+        return (Vec::new(), u.to_vec());
+    }
+    if v.len() == 1 {
+        let mut quotient = u.to_vec();
+        let remainder = div_rem_small(&mut quotient, v[0]);
+        return (quotient, split_u64(u64::from(remainder)));
+    }
+
+    // Normalizes the divisor so that its top digit has the high bit set,
+    // which keeps the estimated quotient digits off by at most two.
+    let shift = v[v.len() - 1].leading_zeros();
+    let v = shl_bits(v, shift as usize);
+    let len = u.len();
+    let mut u = shl_bits(u, shift as usize);
+    u.resize(len + 1, 0);
+
+    let n = v.len();
+    let m = u.len() - n - 1;
+    let base = 1u64 << 32;
+    let mut quotient = vec![0u32; m + 1];
+    for j in (0..=m).rev() {
+        let top = (u64::from(u[j + n]) << 32) | u64::from(u[j + n - 1]);
+        let mut estimate = top / u64::from(v[n - 1]);
+        let mut rest = top % u64::from(v[n - 1]);
+        while estimate >= base
+            || estimate * u64::from(v[n - 2]) > ((rest << 32) | u64::from(u[j + n - 2]))
+        {
+            estimate -= 1;
+            rest += u64::from(v[n - 1]);
+            if rest >= base {
+                break;
+            }
+        }
+
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let product = estimate * u64::from(v[i]) + carry;
+            carry = product >> 32;
+            let total = i64::from(u[i + j]) - borrow - (product & 0xffff_ffff) as i64;
+            u[i + j] = total as u32;
+            borrow = if total < 0 { 1 } else { 0 };
+        }
+        let total = i64::from(u[j + n]) - borrow - carry as i64;
+        u[j + n] = total as u32;
+
+        // The estimate was one too large, adds the divisor back.
+        if total < 0 {
+            estimate -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u64::from(u[i + j]) + u64::from(v[i]) + carry;
+                u[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = estimate as u32;
+    }
+
+    trim(&mut quotient);
+    (quotient, shr_small(&u[..n], shift))
+}
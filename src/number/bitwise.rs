@@ -0,0 +1,120 @@
+use super::{BigInt, Number};
+use crate::sexp::Error;
+use std::convert::TryFrom;
+
+/// Largest number of bits an integer may be shifted left by, or a bit field
+/// of a negative integer may span, to keep
+/// `(arithmetic-shift 1 999999999999)` from exhausting memory.
+const MAX_SHIFT: i64 = 1 << 24;
+
+/// Bitwise operations treat exact integers as infinite two's complement
+/// bit strings, so negative numbers have infinitely many leading ones.
+impl Number {
+    pub fn bitwise_and(&self, other: &Number) -> Result<Number, Error> {
+        if let (Number::Int(a), Number::Int(b)) = (self, other) {
+            return Ok(Number::Int(a & b));
+        }
+        Ok(Number::from(
+            self.exact_integer()?.and(&other.exact_integer()?),
+        ))
+    }
+
+    pub fn bitwise_or(&self, other: &Number) -> Result<Number, Error> {
+        if let (Number::Int(a), Number::Int(b)) = (self, other) {
+            return Ok(Number::Int(a | b));
+        }
+        Ok(Number::from(
+            self.exact_integer()?.or(&other.exact_integer()?),
+        ))
+    }
+
+    pub fn bitwise_xor(&self, other: &Number) -> Result<Number, Error> {
+        if let (Number::Int(a), Number::Int(b)) = (self, other) {
+            return Ok(Number::Int(a ^ b));
+        }
+        Ok(Number::from(
+            self.exact_integer()?.xor(&other.exact_integer()?),
+        ))
+    }
+
+    pub fn bitwise_not(&self) -> Result<Number, Error> {
+        if let Number::Int(i) = self {
+            return Ok(Number::Int(!i));
+        }
+        Ok(Number::from(self.exact_integer()?.not()))
+    }
+
+    /// Multiplies by `2^count`, negative `count` divides rounding towards
+    /// negative infinity. Only left shifts are limited, right shifts past
+    /// the highest bit leave `0` or `-1`.
+    pub fn arithmetic_shift(&self, count: &Number) -> Result<Number, Error> {
+        let n = self.exact_integer()?;
+        let amount = count.exact_integer()?;
+        if amount.is_negative() {
+            let bits = amount
+                .neg()
+                .to_i64()
+                .and_then(|bits| usize::try_from(bits).ok())
+                .unwrap_or(usize::MAX);
+            return Ok(Number::from(n.shr(bits)));
+        }
+        let bits = amount
+            .to_i64()
+            .filter(|bits| *bits <= MAX_SHIFT)
+            .ok_or_else(|| Error::Reason(format!("shift amount too large: {}", count)))?;
+        Ok(Number::from(n.shl(bits as usize)))
+    }
+
+    /// Number of one bits of non-negative integer, or of zero bits of
+    /// negative one.
+    pub fn bit_count(&self) -> Result<Number, Error> {
+        if let Number::Int(i) = self {
+            let ones = if *i < 0 {
+                (!i).count_ones()
+            } else {
+                i.count_ones()
+            };
+            return Ok(Number::Int(ones as i32));
+        }
+        Ok(Number::from(self.exact_integer()?.count_ones() as i64))
+    }
+
+    /// Non-negative integer made of bits `start` (inclusive) to `end`
+    /// (exclusive) of the number.
+    pub fn bit_field(&self, start: &Number, end: &Number) -> Result<Number, Error> {
+        let n = self.exact_integer()?;
+        let index = |bound: &Number| {
+            bound
+                .exact_integer()?
+                .to_i64()
+                .and_then(|i| usize::try_from(i).ok())
+                .ok_or_else(|| {
+                    Error::Reason(format!("expected non-negative bit index, got: {}", bound))
+                })
+        };
+        let (from, to) = (index(start)?, index(end)?);
+        if from > to {
+            return Err(Error::Reason(format!(
+                "bit field start {} is after its end {}",
+                start, end
+            )));
+        }
+        let shifted = n.shr(from);
+        // Bits above the magnitude of non-negative number are all zero.
+        let width = if shifted.is_negative() {
+            to - from
+        } else {
+            (to - from).min(shifted.bit_length())
+        };
+        if width > MAX_SHIFT as usize {
+            return Err(Error::Reason(format!("bit field too large: {}", end)));
+        }
+        let mask = BigInt::from(1).shl(width).sub(&BigInt::from(1));
+        Ok(Number::from(shifted.and(&mask)))
+    }
+
+    fn exact_integer(&self) -> Result<BigInt, Error> {
+        self.to_big()
+            .ok_or_else(|| Error::Reason(format!("expected exact integer, got: {}", self)))
+    }
+}
@@ -0,0 +1,223 @@
+use super::{format_float, BigInt, Number, Rational};
+use std::fmt;
+
+/// Inexact complex number in rectangular form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    pub fn from_polar(magnitude: f64, angle: f64) -> Complex {
+        Complex::new(magnitude * angle.cos(), magnitude * angle.sin())
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    pub fn angle(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn add(&self, other: &Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+
+    pub fn sub(&self, other: &Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+
+    pub fn mul(&self, other: &Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+
+    pub fn div(&self, other: &Complex) -> Complex {
+        let norm = other.re * other.re + other.im * other.im;
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / norm,
+            (self.im * other.re - self.re * other.im) / norm,
+        )
+    }
+
+    /// Principal square root, its real part is never negative.
+    pub fn sqrt(&self) -> Complex {
+        let magnitude = self.magnitude();
+        Complex::new(
+            ((magnitude + self.re) / 2.0).sqrt(),
+            ((magnitude - self.re) / 2.0).sqrt().copysign(self.im),
+        )
+    }
+
+    pub fn exp(&self) -> Complex {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Principal natural logarithm, its imaginary part is in `(-pi, pi]`.
+    pub fn ln(&self) -> Complex {
+        Complex::new(self.magnitude().ln(), self.angle())
+    }
+
+    /// Principal value of `self` raised to the power of `exponent`.
+    pub fn pow(&self, exponent: &Complex) -> Complex {
+        exponent.mul(&self.ln()).exp()
+    }
+
+    pub fn sin(&self) -> Complex {
+        Complex::new(
+            self.re.sin() * self.im.cosh(),
+            self.re.cos() * self.im.sinh(),
+        )
+    }
+
+    pub fn cos(&self) -> Complex {
+        Complex::new(
+            self.re.cos() * self.im.cosh(),
+            -self.re.sin() * self.im.sinh(),
+        )
+    }
+
+    pub fn tan(&self) -> Complex {
+        self.sin().div(&self.cos())
+    }
+
+    /// Principal arcsine, `-i ln(iz + sqrt(1 - z^2))`.
+    pub fn asin(&self) -> Complex {
+        let i = Complex::new(0.0, 1.0);
+        let one = Complex::new(1.0, 0.0);
+        let root = one.sub(&self.mul(self)).sqrt();
+        let ln = i.mul(self).add(&root).ln();
+        Complex::new(ln.im, -ln.re)
+    }
+
+    /// Principal arccosine, `pi/2 - asin(z)`.
+    pub fn acos(&self) -> Complex {
+        Complex::new(std::f64::consts::FRAC_PI_2, 0.0).sub(&self.asin())
+    }
+
+    /// Principal arctangent, `i/2 (ln(1 - iz) - ln(1 + iz))`.
+    pub fn atan(&self) -> Complex {
+        let one = Complex::new(1.0, 0.0);
+        let iz = Complex::new(-self.im, self.re);
+        let difference = one.sub(&iz).ln().sub(&one.add(&iz).ln());
+        Complex::new(-difference.im / 2.0, difference.re / 2.0)
+    }
+}
+
+/// Written as `re+imi` with a zero real part left out, so `(sqrt -2.0)` is
+/// written as `+1.4142135623730951i`.
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.re != 0.0 {
+            write!(f, "{}", format_float(self.re))?;
+        }
+        write_imaginary(f, &format_float(self.im))
+    }
+}
+
+/// Exact complex number in rectangular form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExactComplex {
+    pub re: Rational,
+    pub im: Rational,
+}
+
+impl ExactComplex {
+    pub fn new(re: Rational, im: Rational) -> ExactComplex {
+        ExactComplex { re, im }
+    }
+
+    pub fn to_complex(&self) -> Complex {
+        Complex::new(self.re.to_f64(), self.im.to_f64())
+    }
+
+    pub fn add(&self, other: &ExactComplex) -> ExactComplex {
+        ExactComplex::new(self.re.add(&other.re), self.im.add(&other.im))
+    }
+
+    pub fn sub(&self, other: &ExactComplex) -> ExactComplex {
+        ExactComplex::new(self.re.sub(&other.re), self.im.sub(&other.im))
+    }
+
+    pub fn mul(&self, other: &ExactComplex) -> ExactComplex {
+        ExactComplex::new(
+            self.re.mul(&other.re).sub(&self.im.mul(&other.im)),
+            self.re.mul(&other.im).add(&self.im.mul(&other.re)),
+        )
+    }
+
+    /// Quotient, `None` when `other` is zero.
+    pub fn div(&self, other: &ExactComplex) -> Option<ExactComplex> {
+        let norm = other.re.mul(&other.re).add(&other.im.mul(&other.im));
+        Some(ExactComplex::new(
+            self.re
+                .mul(&other.re)
+                .add(&self.im.mul(&other.im))
+                .div(&norm)?,
+            self.im
+                .mul(&other.re)
+                .sub(&self.re.mul(&other.im))
+                .div(&norm)?,
+        ))
+    }
+
+    /// Raises the number to a non-negative integer power by repeated
+    /// squaring.
+    pub fn pow(&self, mut exponent: u64) -> ExactComplex {
+        let zero = Rational::from(BigInt::zero());
+        let mut result = ExactComplex::new(Rational::from(BigInt::from(1)), zero);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.mul(&base);
+            }
+            exponent /= 2;
+            if exponent > 0 {
+                base = base.mul(&base);
+            }
+        }
+        result
+    }
+
+    /// Length in bits of the largest numerator or denominator of the parts.
+    pub fn bit_length(&self) -> usize {
+        [&self.re, &self.im]
+            .iter()
+            .map(|r| r.numerator().bit_length().max(r.denominator().bit_length()))
+            .max()
+            .expect("two parts")
+    }
+}
+
+/// Written like `Complex` with exact parts and a unit imaginary part written
+/// as a bare sign, so `(sqrt -1)` is written as `+i`.
+impl fmt::Display for ExactComplex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let re = Number::from(self.re.clone());
+        if !re.is_zero() {
+            write!(f, "{}", re)?;
+        }
+        match Number::from(self.im.clone()).to_string().as_str() {
+            "1" => write!(f, "+i"),
+            "-1" => write!(f, "-i"),
+            im => write_imaginary(f, im),
+        }
+    }
+}
+
+/// Writes imaginary part with its sign and the `i` suffix.
+fn write_imaginary(f: &mut fmt::Formatter, im: &str) -> fmt::Result {
+    if im.starts_with(['+', '-']) {
+        write!(f, "{}i", im)
+    } else {
+        write!(f, "+{}i", im)
+    }
+}
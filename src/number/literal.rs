@@ -0,0 +1,210 @@
+use super::{BigInt, Number, Rational};
+use crate::sexp::Error;
+
+/// Largest power of ten an exact decimal numeral may be scaled by, to keep
+/// numerals like `#e1e999999999` from exhausting memory.
+const MAX_EXACT_EXPONENT: i64 = 10_000;
+
+impl Number {
+    /// Reads numeral `text` written in `radix`, which may be overridden by
+    /// a `#x`, `#b`, `#o` or `#d` prefix. Exactness is taken from the numeral
+    /// unless forced with `#e` or `#i`. Digits may be separated with
+    /// underscores, as in `1_000_000`.
+    pub fn parse(text: &str, radix: u32) -> Result<Number, Error> {
+        let malformed = |reason: String| Error::Reason(format!("{} in numeral: {}", reason, text));
+
+        let mut body = text;
+        let mut radix_prefix = None;
+        let mut exactness = None;
+        while let Some(rest) = body.strip_prefix('#') {
+            let mut chars = rest.chars();
+            let prefix = chars.next().map(|c| c.to_ascii_lowercase());
+            match prefix {
+                Some(c @ ('x' | 'b' | 'o' | 'd')) => {
+                    if radix_prefix.is_some() {
+                        return Err(malformed("duplicate radix prefix".to_owned()));
+                    }
+                    radix_prefix = Some(match c {
+                        'x' => 16,
+                        'b' => 2,
+                        'o' => 8,
+                        _ => 10,
+                    });
+                }
+                Some(c @ ('e' | 'i')) => {
+                    if exactness.is_some() {
+                        return Err(malformed("duplicate exactness prefix".to_owned()));
+                    }
+                    exactness = Some(c == 'e');
+                }
+                _ => return Err(malformed(format!("unknown prefix #{}", rest))),
+            }
+            body = chars.as_str();
+        }
+
+        let radix = radix_prefix.unwrap_or(radix);
+        let exact = exactness == Some(true);
+        let number = parse_complex(body, radix, exact).map_err(malformed)?;
+        match exactness {
+            Some(true) => number.to_exact(),
+            Some(false) => Ok(number.to_inexact()),
+            None => Ok(number),
+        }
+    }
+}
+
+/// Reads real number, or complex one in rectangular form such as `1+2i` and
+/// `-i`, or in polar form such as `1@2`.
+fn parse_complex(text: &str, radix: u32, exact: bool) -> Result<Number, String> {
+    if let Some(i) = text.find('@') {
+        let magnitude = parse_real(&text[..i], radix, exact)?;
+        let angle = parse_real(&text[i + 1..], radix, exact)?;
+        return Number::make_polar(&magnitude, &angle).map_err(|_| "invalid complex".to_owned());
+    }
+
+    let body = match text.strip_suffix('i') {
+        Some(body) => body,
+        None => return parse_real(text, radix, exact),
+    };
+    // Imaginary part starts at the last sign that isn't part of an exponent.
+    let split = body
+        .char_indices()
+        .rev()
+        .find(|(i, c)| {
+            let exponent = radix == 10 && body[..*i].ends_with(['e', 'E']);
+            matches!(c, '+' | '-') && !exponent
+        })
+        .map(|(i, _)| i)
+        .ok_or_else(|| "missing sign of imaginary part".to_owned())?;
+
+    let (re, im) = body.split_at(split);
+    let re = match re {
+        "" => Number::Int(0),
+        _ => parse_real(re, radix, exact)?,
+    };
+    let im = match im {
+        "+" => Number::Int(1),
+        "-" => Number::Int(-1),
+        _ => parse_real(im, radix, exact)?,
+    };
+    Number::make_rectangular(&re, &im).map_err(|_| "invalid complex".to_owned())
+}
+
+/// Reads optionally signed integer, fraction or decimal. Decimals are read
+/// exactly when `exact` is set, so `#e0.1` is `1/10` rather than the nearest
+/// float.
+fn parse_real(text: &str, radix: u32, exact: bool) -> Result<Number, String> {
+    match text.to_ascii_lowercase().as_str() {
+        "+inf.0" => return Ok(Number::Float(f64::INFINITY)),
+        "-inf.0" => return Ok(Number::Float(f64::NEG_INFINITY)),
+        "+nan.0" | "-nan.0" => return Ok(Number::Float(f64::NAN)),
+        _ => (),
+    }
+
+    let (negative, unsigned) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let magnitude = if let Some(i) = unsigned.find('/') {
+        let numerator = parse_digits(&unsigned[..i], radix)?;
+        let denominator = parse_digits(&unsigned[i + 1..], radix)?;
+        Rational::new(numerator, denominator)
+            .map(Number::from)
+            .ok_or_else(|| "division by zero".to_owned())?
+    } else if unsigned.contains('.') || (radix == 10 && unsigned.contains(['e', 'E'])) {
+        if radix != 10 {
+            return Err(format!("decimal point in radix {}", radix));
+        }
+        parse_decimal(unsigned, exact)?
+    } else {
+        Number::from(parse_digits(unsigned, radix)?)
+    };
+
+    if !negative {
+        return Ok(magnitude);
+    }
+    match magnitude {
+        Number::Float(f) => Ok(Number::Float(-f)),
+        n => Number::Int(0)
+            .checked_sub(&n)
+            .map_err(|_| "invalid number".to_owned()),
+    }
+}
+
+/// Reads unsigned decimal such as `1.5`, `.5` or `15e-1`.
+fn parse_decimal(text: &str, exact: bool) -> Result<Number, String> {
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(i) => (&text[..i], Some(&text[i + 1..])),
+        None => (text, None),
+    };
+    let (whole, fraction) = match mantissa.find('.') {
+        Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+        None => (mantissa, ""),
+    };
+    if whole.is_empty() && fraction.is_empty() {
+        return Err("missing digits".to_owned());
+    }
+    for part in [whole, fraction].iter().filter(|part| !part.is_empty()) {
+        parse_digits(part, 10)?;
+    }
+    let exponent = match exponent {
+        Some(exponent) => {
+            let (negative, digits) = match exponent.as_bytes().first() {
+                Some(b'-') => (true, &exponent[1..]),
+                Some(b'+') => (false, &exponent[1..]),
+                _ => (false, exponent),
+            };
+            let value = parse_digits(digits, 10)?
+                .to_i64()
+                .ok_or_else(|| "exponent too large".to_owned())?;
+            if negative {
+                -value
+            } else {
+                value
+            }
+        }
+        None => 0,
+    };
+
+    if !exact {
+        return text
+            .replace('_', "")
+            .parse()
+            .map(Number::Float)
+            .map_err(|_| "invalid decimal".to_owned());
+    }
+
+    let digits = format!("{}{}", whole, fraction).replace('_', "");
+    let significand = BigInt::parse(&digits, 10).expect("digits checked above");
+    let scale = exponent - fraction.replace('_', "").len() as i64;
+    if scale.abs() > MAX_EXACT_EXPONENT {
+        return Err("exponent too large for exact number".to_owned());
+    }
+    let power = BigInt::parse(
+        &format!("1{}", "0".repeat(scale.unsigned_abs() as usize)),
+        10,
+    )
+    .expect("power of ten is valid");
+    let value = if scale >= 0 {
+        Rational::from(significand.mul(&power))
+    } else {
+        Rational::new(significand, power).expect("power of ten is not zero")
+    };
+    Ok(Number::from(value))
+}
+
+/// Reads unsigned digits in `radix`, checking that underscores only ever
+/// separate two digits.
+fn parse_digits(text: &str, radix: u32) -> Result<BigInt, String> {
+    if text.is_empty() {
+        return Err("missing digits".to_owned());
+    }
+    if text.starts_with('_') || text.ends_with('_') || text.contains("__") {
+        return Err("misplaced digit separator".to_owned());
+    }
+    if let Some(c) = text.chars().find(|c| *c != '_' && !c.is_digit(radix)) {
+        return Err(format!("invalid digit {:?} for radix {}", c, radix));
+    }
+    Ok(BigInt::parse(&text.replace('_', ""), radix).expect("digits checked above"))
+}
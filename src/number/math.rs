@@ -0,0 +1,317 @@
+use super::{division_by_zero, BigInt, ExactComplex, Number, Rational};
+use crate::sexp::Error;
+use std::cmp::Ordering;
+
+/// Largest number of bits an exact power may take. Multiplication of big
+/// integers is quadratic, so this keeps `(expt 3 1000000)` from running for
+/// seconds and `(expt 2 1000000000000)` from exhausting memory.
+const MAX_POWER_BITS: u64 = 1 << 16;
+
+/// Transcendental functions yield inexact results, except for exact square
+/// roots and exact integer powers of exact numbers. Functions leave the real
+/// line where their real counterpart is undefined, so `(sqrt -1)` is `+i` and
+/// `(asin 2)` is complex.
+impl Number {
+    /// Principal square root. Exact squares of exact numbers have exact
+    /// roots, negative numbers have imaginary ones, so `(sqrt -4)` is `+2i`.
+    pub fn sqrt(&self) -> Number {
+        if let Some(root) = self.exact_sqrt() {
+            return root;
+        }
+        match self {
+            n if n.is_real() && !n.is_negative() => Number::Float(n.to_f64().sqrt()),
+            n => Number::from(n.to_complex().sqrt()),
+        }
+    }
+
+    fn exact_sqrt(&self) -> Option<Number> {
+        let r = self.to_rational()?;
+        if r.numerator().is_negative() {
+            let root = Number::from(r.neg()).exact_sqrt()?.to_rational()?;
+            return Some(Number::from(ExactComplex::new(
+                Rational::from(BigInt::zero()),
+                root,
+            )));
+        }
+        let numerator = r.numerator().isqrt();
+        let denominator = r.denominator().isqrt();
+        if numerator.mul(&numerator) != *r.numerator()
+            || denominator.mul(&denominator) != *r.denominator()
+        {
+            return None;
+        }
+        Rational::new(numerator, denominator).map(Number::from)
+    }
+
+    /// Root `s` and remainder `r` of exact non-negative integer `n`, such
+    /// that `n = s^2 + r` and `n < (s + 1)^2`.
+    pub fn exact_integer_sqrt(&self) -> Result<(Number, Number), Error> {
+        match self.to_big() {
+            Some(n) if !n.is_negative() => {
+                let root = n.isqrt();
+                let remainder = n.sub(&root.mul(&root));
+                Ok((Number::from(root), Number::from(remainder)))
+            }
+            _ => Err(Error::Reason(format!(
+                "expected exact non-negative integer, got: {}",
+                self
+            ))),
+        }
+    }
+
+    /// Raises `self` to the power of `exponent`, exact numbers raised to
+    /// exact integer powers stay exact.
+    pub fn expt(&self, exponent: &Number) -> Result<Number, Error> {
+        if let (Some(base), Some(power)) = (self.to_rational(), exponent.to_big()) {
+            let bits = base
+                .numerator()
+                .bit_length()
+                .max(base.denominator().bit_length()) as u64;
+            let power = match power.to_i64() {
+                Some(power)
+                    if bits <= 1 || bits.saturating_mul(power.unsigned_abs()) <= MAX_POWER_BITS =>
+                {
+                    power
+                }
+                // Powers of 0, 1 and -1 only depend on the sign and parity.
+                _ if bits <= 1 => {
+                    let small = if power.is_even() { 2 } else { 1 };
+                    if power.is_negative() {
+                        -small
+                    } else {
+                        small
+                    }
+                }
+                _ => return Err(Error::Reason(format!("exponent too large: {}", exponent))),
+            };
+            let result = base.pow(power.unsigned_abs());
+            if power >= 0 {
+                return Ok(Number::from(result));
+            }
+            return Rational::from(BigInt::from(1))
+                .div(&result)
+                .map(Number::from)
+                .ok_or_else(division_by_zero);
+        }
+
+        if let (Number::ExactComplex(base), Some(power)) = (self, exponent.to_big()) {
+            let power = match power.to_i64() {
+                Some(power)
+                    if (base.bit_length() as u64).saturating_mul(power.unsigned_abs())
+                        <= MAX_POWER_BITS =>
+                {
+                    power
+                }
+                _ => return Err(Error::Reason(format!("exponent too large: {}", exponent))),
+            };
+            let result = Number::from(base.pow(power.unsigned_abs()));
+            if power >= 0 {
+                return Ok(result);
+            }
+            return Number::Int(1).checked_div(&result);
+        }
+
+        if self.is_real() && exponent.is_real() {
+            let (base, power) = (self.to_f64(), exponent.to_f64());
+            if base >= 0.0 || power.fract() == 0.0 || !power.is_finite() {
+                return Ok(Number::Float(base.powf(power)));
+            }
+        }
+        if self.is_zero() {
+            return Ok(Number::Float(0.0));
+        }
+        Ok(Number::from(self.to_complex().pow(&exponent.to_complex())))
+    }
+
+    pub fn exp(&self) -> Number {
+        match self {
+            n if !n.is_real() => Number::from(n.to_complex().exp()),
+            n => Number::Float(n.to_f64().exp()),
+        }
+    }
+
+    /// Principal natural logarithm, negative numbers have complex logarithms.
+    pub fn ln(&self) -> Number {
+        match self {
+            n if n.is_real() && !n.is_negative() => Number::Float(n.to_f64().ln()),
+            n => Number::from(n.to_complex().ln()),
+        }
+    }
+
+    /// Logarithm of `self` to the given `base`.
+    pub fn log(&self, base: &Number) -> Result<Number, Error> {
+        self.ln().checked_div(&base.ln())
+    }
+
+    pub fn sin(&self) -> Number {
+        match self {
+            n if !n.is_real() => Number::from(n.to_complex().sin()),
+            n => Number::Float(n.to_f64().sin()),
+        }
+    }
+
+    pub fn cos(&self) -> Number {
+        match self {
+            n if !n.is_real() => Number::from(n.to_complex().cos()),
+            n => Number::Float(n.to_f64().cos()),
+        }
+    }
+
+    pub fn tan(&self) -> Number {
+        match self {
+            n if !n.is_real() => Number::from(n.to_complex().tan()),
+            n => Number::Float(n.to_f64().tan()),
+        }
+    }
+
+    pub fn asin(&self) -> Number {
+        match self.to_f64() {
+            f if (-1.0..=1.0).contains(&f) => Number::Float(f.asin()),
+            _ => Number::from(self.to_complex().asin()),
+        }
+    }
+
+    pub fn acos(&self) -> Number {
+        match self.to_f64() {
+            f if (-1.0..=1.0).contains(&f) => Number::Float(f.acos()),
+            _ => Number::from(self.to_complex().acos()),
+        }
+    }
+
+    pub fn atan(&self) -> Number {
+        match self {
+            n if !n.is_real() => Number::from(n.to_complex().atan()),
+            n => Number::Float(n.to_f64().atan()),
+        }
+    }
+
+    /// Angle of point `(x, y)` from positive x axis, where `self` is `y`.
+    pub fn atan2(&self, x: &Number) -> Result<Number, Error> {
+        Ok(Number::Float(
+            self.real()?.to_f64().atan2(x.real()?.to_f64()),
+        ))
+    }
+
+    /// Largest integer not greater than the number.
+    pub fn floor(&self) -> Result<Number, Error> {
+        self.round_by(f64::floor, |floor, _| floor)
+    }
+
+    /// Smallest integer not less than the number.
+    pub fn ceiling(&self) -> Result<Number, Error> {
+        self.round_by(f64::ceil, |floor, _| floor.add(&BigInt::from(1)))
+    }
+
+    /// Integer nearest to the number towards zero.
+    pub fn truncate(&self) -> Result<Number, Error> {
+        self.round_by(f64::trunc, |floor, r| {
+            if r.numerator().is_negative() {
+                return floor.add(&BigInt::from(1));
+            }
+            floor
+        })
+    }
+
+    /// Nearest integer, halfway cases are rounded to the even one.
+    pub fn round(&self) -> Result<Number, Error> {
+        self.round_by(f64::round_ties_even, |floor, r| {
+            let half = Rational::new(BigInt::from(1), BigInt::from(2)).expect("two is not zero");
+            let rounded_up = floor.add(&BigInt::from(1));
+            match r.sub(&Rational::from(floor.clone())).cmp(&half) {
+                Ordering::Less => floor,
+                Ordering::Greater => rounded_up,
+                Ordering::Equal if floor.is_even() => floor,
+                Ordering::Equal => rounded_up,
+            }
+        })
+    }
+
+    /// Rounds real number to an integer. Floats are rounded by `float`, for
+    /// fractions `fraction` picks the integer given the floor of the
+    /// fraction. Integers are already rounded.
+    fn round_by(
+        &self,
+        float: fn(f64) -> f64,
+        fraction: fn(BigInt, &Rational) -> BigInt,
+    ) -> Result<Number, Error> {
+        match self.real()? {
+            Number::Float(f) => Ok(Number::Float(float(*f))),
+            Number::Rational(r) => {
+                let (quotient, remainder) = r
+                    .numerator()
+                    .div_rem(r.denominator())
+                    .expect("denominator is not zero");
+                let floor = if remainder.is_negative() {
+                    quotient.sub(&BigInt::from(1))
+                } else {
+                    quotient
+                };
+                Ok(Number::from(fraction(floor, r)))
+            }
+            n => Ok(n.clone()),
+        }
+    }
+
+    /// Value of integer as exact integer, floats with integral value are
+    /// accepted.
+    fn integer(&self) -> Result<BigInt, Error> {
+        match self {
+            Number::Float(f) if self.is_integer() => {
+                Ok(BigInt::from_f64(*f).expect("float is integral"))
+            }
+            _ => self
+                .to_big()
+                .ok_or_else(|| Error::Reason(format!("expected integer, got: {}", self))),
+        }
+    }
+
+    /// Greatest common divisor, inexact when either argument is.
+    pub fn gcd(&self, other: &Number) -> Result<Number, Error> {
+        let gcd = Number::from(self.integer()?.gcd(&other.integer()?));
+        Ok(self.inexact_if_either(other, gcd))
+    }
+
+    /// Least common multiple, inexact when either argument is.
+    pub fn lcm(&self, other: &Number) -> Result<Number, Error> {
+        let (a, b) = (self.integer()?, other.integer()?);
+        let lcm = if a.is_zero() || b.is_zero() {
+            BigInt::zero()
+        } else {
+            let (quotient, _) = a
+                .mul(&b)
+                .abs()
+                .div_rem(&a.gcd(&b))
+                .expect("gcd is not zero");
+            quotient
+        };
+        Ok(self.inexact_if_either(other, Number::from(lcm)))
+    }
+
+    fn inexact_if_either(&self, other: &Number, result: Number) -> Number {
+        if self.is_exact() && other.is_exact() {
+            return result;
+        }
+        result.to_inexact()
+    }
+
+    /// Writes the number in `radix`, only exact real numbers can be written
+    /// in radix other than 10.
+    pub fn to_str_radix(&self, radix: u32) -> Result<String, Error> {
+        match self {
+            _ if radix == 10 => Ok(self.to_string()),
+            Number::Int(_) | Number::Big(_) => Ok(self
+                .to_big()
+                .expect("number is an exact integer")
+                .to_str_radix(radix)),
+            Number::Rational(r) => Ok(format!(
+                "{}/{}",
+                r.numerator().to_str_radix(radix),
+                r.denominator().to_str_radix(radix)
+            )),
+            _ => Err(Error::Reason(format!(
+                "inexact number can only be written in radix 10, got: {}",
+                self
+            ))),
+        }
+    }
+}
@@ -0,0 +1,485 @@
+use super::sexp::Error;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+mod bigint;
+pub use bigint::BigInt;
+mod complex;
+pub use complex::{Complex, ExactComplex};
+mod bitwise;
+mod literal;
+mod math;
+mod rational;
+pub use rational::Rational;
+
+#[derive(Debug, Clone)]
+pub enum Number {
+    Int(i32),
+    /// Integer that doesn't fit in `Int`. Results of arithmetic are demoted
+    /// back to `Int` whenever they fit, so the two never overlap.
+    Big(BigInt),
+    /// Exact fraction that isn't an integer, integral results of arithmetic
+    /// are demoted to `Int` or `Big`.
+    Rational(Rational),
+    Float(f64),
+    /// Complex number with non-zero imaginary part, results with zero
+    /// imaginary part are demoted to `Float`.
+    Complex(Complex),
+    /// Complex number with exact parts and non-zero imaginary part, results
+    /// with zero imaginary part are demoted to real exact numbers.
+    ExactComplex(ExactComplex),
+}
+
+/// Numbers are compared by value regardless of exactness, so `1` equals
+/// `1.0`. NaN is not equal to anything, itself included.
+impl PartialEq for Number {
+    fn eq(&self, other: &Number) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => Some(a.cmp(b)),
+            // Complex numbers are unordered, they can only be equal.
+            _ if !self.is_real() || !other.is_real() => {
+                if self.real_part() == other.real_part() && self.imag_part() == other.imag_part() {
+                    return Some(Ordering::Equal);
+                }
+                None
+            }
+            (Number::Float(a), Number::Float(b)) => a.partial_cmp(b),
+            (Number::Float(f), _) => {
+                cmp_exact_float(&other.to_rational()?, *f).map(Ordering::reverse)
+            }
+            (_, Number::Float(f)) => cmp_exact_float(&self.to_rational()?, *f),
+            _ => Some(self.to_rational()?.cmp(&other.to_rational()?)),
+        }
+    }
+}
+
+/// Compares exact number with float without rounding the exact one.
+fn cmp_exact_float(exact: &Rational, f: f64) -> Option<Ordering> {
+    if f.is_nan() {
+        return None;
+    } else if f.is_infinite() {
+        return Some(if f > 0.0 {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        });
+    }
+    Rational::from_f64(f).map(|f| exact.cmp(&f))
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Number::Int(i) => write!(f, "{}", i),
+            Number::Big(b) => write!(f, "{}", b),
+            Number::Rational(r) => write!(f, "{}", r),
+            Number::Float(k) => write!(f, "{}", format_float(*k)),
+            Number::Complex(c) => write!(f, "{}", c),
+            Number::ExactComplex(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+/// Writes float the way it is read, infinities as `+inf.0` and `-inf.0` and
+/// NaN as `+nan.0`.
+pub(crate) fn format_float(f: f64) -> String {
+    if f.is_nan() {
+        "+nan.0".to_owned()
+    } else if f.is_infinite() {
+        if f > 0.0 { "+inf.0" } else { "-inf.0" }.to_owned()
+    } else {
+        // Debug keeps a `.0` or an exponent, so the text reads back inexact.
+        format!("{:?}", f)
+    }
+}
+
+impl From<i64> for Number {
+    fn from(i: i64) -> Number {
+        match i32::try_from(i) {
+            Ok(i) => Number::Int(i),
+            Err(_) => Number::Big(BigInt::from(i)),
+        }
+    }
+}
+
+impl From<BigInt> for Number {
+    fn from(b: BigInt) -> Number {
+        match b.to_i32() {
+            Some(i) => Number::Int(i),
+            None => Number::Big(b),
+        }
+    }
+}
+
+impl From<Rational> for Number {
+    fn from(r: Rational) -> Number {
+        if r.is_integer() {
+            return Number::from(r.numerator().clone());
+        }
+        Number::Rational(r)
+    }
+}
+
+impl From<Complex> for Number {
+    fn from(c: Complex) -> Number {
+        if c.im == 0.0 {
+            return Number::Float(c.re);
+        }
+        Number::Complex(c)
+    }
+}
+
+impl From<ExactComplex> for Number {
+    fn from(c: ExactComplex) -> Number {
+        if c.im.numerator().is_zero() {
+            return Number::from(c.re);
+        }
+        Number::ExactComplex(c)
+    }
+}
+
+pub(crate) fn division_by_zero() -> Error {
+    Error::Reason("division by zero".to_owned())
+}
+
+/// Arithmetic follows contagion rules: operation on two exact numbers yields
+/// an exact number, anything involving a float yields a float. Integers never
+/// overflow, results too large for `Int` are promoted to `Big`. Exact
+/// complex numbers follow the same rules, inexact ones are contagious like
+/// floats.
+impl Number {
+    /// Value of real number as a float, NaN for complex numbers.
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Number::Int(i) => f64::from(*i),
+            Number::Big(b) => b.to_f64(),
+            Number::Rational(r) => r.to_f64(),
+            Number::Float(f) => *f,
+            Number::Complex(_) | Number::ExactComplex(_) => f64::NAN,
+        }
+    }
+
+    pub fn to_complex(&self) -> Complex {
+        match self {
+            Number::Complex(c) => *c,
+            Number::ExactComplex(c) => c.to_complex(),
+            n => Complex::new(n.to_f64(), 0.0),
+        }
+    }
+
+    /// Value of exact number as a complex one, `None` for inexact numbers.
+    pub fn to_exact_complex(&self) -> Option<ExactComplex> {
+        match self {
+            Number::ExactComplex(c) => Some(c.clone()),
+            n => Some(ExactComplex::new(
+                n.to_rational()?,
+                Rational::from(BigInt::zero()),
+            )),
+        }
+    }
+
+    pub fn is_real(&self) -> bool {
+        !matches!(self, Number::Complex(_) | Number::ExactComplex(_))
+    }
+
+    /// Checks that the number is real, for operations undefined on complex
+    /// numbers.
+    pub fn real(&self) -> Result<&Number, Error> {
+        if !self.is_real() {
+            return Err(Error::Reason(format!(
+                "expected real number, got: {}",
+                self
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Complex number `re + im * i`, exact when both parts are. Exact zero
+    /// imaginary part yields `re` unchanged.
+    pub fn make_rectangular(re: &Number, im: &Number) -> Result<Number, Error> {
+        if im.is_exact() && im.is_zero() {
+            return Ok(re.real()?.clone());
+        }
+        if let (Some(re), Some(im)) = (re.real()?.to_rational(), im.real()?.to_rational()) {
+            return Ok(Number::from(ExactComplex::new(re, im)));
+        }
+        Ok(Number::from(Complex::new(
+            re.real()?.to_f64(),
+            im.real()?.to_f64(),
+        )))
+    }
+
+    /// Complex number with given magnitude and angle, exact zero angle
+    /// yields `magnitude` unchanged.
+    pub fn make_polar(magnitude: &Number, angle: &Number) -> Result<Number, Error> {
+        if angle.is_exact() && angle.is_zero() {
+            return Ok(magnitude.real()?.clone());
+        }
+        Ok(Number::from(Complex::from_polar(
+            magnitude.real()?.to_f64(),
+            angle.real()?.to_f64(),
+        )))
+    }
+
+    pub fn real_part(&self) -> Number {
+        match self {
+            Number::Complex(c) => Number::Float(c.re),
+            Number::ExactComplex(c) => Number::from(c.re.clone()),
+            n => n.clone(),
+        }
+    }
+
+    /// Imaginary part, exact zero for real numbers.
+    pub fn imag_part(&self) -> Number {
+        match self {
+            Number::Complex(c) => Number::Float(c.im),
+            Number::ExactComplex(c) => Number::from(c.im.clone()),
+            _ => Number::Int(0),
+        }
+    }
+
+    /// Distance from zero, exact when it is an exact square root.
+    pub fn magnitude(&self) -> Result<Number, Error> {
+        match self {
+            Number::Complex(c) => Ok(Number::Float(c.magnitude())),
+            Number::ExactComplex(c) => {
+                Ok(Number::from(c.re.mul(&c.re).add(&c.im.mul(&c.im))).sqrt())
+            }
+            n => n.abs(),
+        }
+    }
+
+    /// Angle from positive real axis, exact zero for non-negative exact
+    /// numbers.
+    pub fn angle(&self) -> Number {
+        match self {
+            Number::Complex(c) => Number::Float(c.angle()),
+            Number::ExactComplex(c) => Number::Float(c.to_complex().angle()),
+            n if n.is_exact() && !n.is_negative() => Number::Int(0),
+            n => Number::Float(0f64.atan2(n.to_f64())),
+        }
+    }
+
+    /// Value of exact integer, `None` for fractions and inexact numbers.
+    pub fn to_big(&self) -> Option<BigInt> {
+        match self {
+            Number::Int(i) => Some(BigInt::from(*i)),
+            Number::Big(b) => Some(b.clone()),
+            _ => None,
+        }
+    }
+
+    /// Value of exact number as a fraction, `None` for inexact numbers.
+    pub fn to_rational(&self) -> Option<Rational> {
+        match self {
+            Number::Rational(r) => Some(r.clone()),
+            _ => self.to_big().map(Rational::from),
+        }
+    }
+
+    /// Exact number closest to inexact one, exact numbers are unchanged.
+    /// Infinities and NaN have no exact counterpart.
+    pub fn to_exact(&self) -> Result<Number, Error> {
+        match self {
+            Number::Float(f) => Rational::from_f64(*f)
+                .map(Number::from)
+                .ok_or_else(|| Error::Reason(format!("no exact representation of: {}", self))),
+            Number::Complex(c) => Rational::from_f64(c.re)
+                .zip(Rational::from_f64(c.im))
+                .map(|(re, im)| Number::from(ExactComplex::new(re, im)))
+                .ok_or_else(|| Error::Reason(format!("no exact representation of: {}", self))),
+            _ => Ok(self.clone()),
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        !matches!(self, Number::Float(_) | Number::Complex(_))
+    }
+
+    /// Converts exact number to inexact one, inexact numbers are unchanged.
+    pub fn to_inexact(&self) -> Number {
+        match self {
+            Number::Complex(c) => Number::Complex(*c),
+            Number::ExactComplex(c) => Number::Complex(c.to_complex()),
+            n => Number::Float(n.to_f64()),
+        }
+    }
+
+    pub fn checked_add(&self, other: &Number) -> Result<Number, Error> {
+        Ok(match (self, other) {
+            (Number::Int(a), Number::Int(b)) => Number::from(i64::from(*a) + i64::from(*b)),
+            _ if !self.is_real() || !other.is_real() => {
+                match (self.to_exact_complex(), other.to_exact_complex()) {
+                    (Some(a), Some(b)) => Number::from(a.add(&b)),
+                    _ => Number::from(self.to_complex().add(&other.to_complex())),
+                }
+            }
+            _ => match (self.to_rational(), other.to_rational()) {
+                (Some(a), Some(b)) => Number::from(a.add(&b)),
+                _ => Number::Float(self.to_f64() + other.to_f64()),
+            },
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Number) -> Result<Number, Error> {
+        Ok(match (self, other) {
+            (Number::Int(a), Number::Int(b)) => Number::from(i64::from(*a) - i64::from(*b)),
+            _ if !self.is_real() || !other.is_real() => {
+                match (self.to_exact_complex(), other.to_exact_complex()) {
+                    (Some(a), Some(b)) => Number::from(a.sub(&b)),
+                    _ => Number::from(self.to_complex().sub(&other.to_complex())),
+                }
+            }
+            _ => match (self.to_rational(), other.to_rational()) {
+                (Some(a), Some(b)) => Number::from(a.sub(&b)),
+                _ => Number::Float(self.to_f64() - other.to_f64()),
+            },
+        })
+    }
+
+    pub fn checked_mul(&self, other: &Number) -> Result<Number, Error> {
+        Ok(match (self, other) {
+            (Number::Int(a), Number::Int(b)) => Number::from(i64::from(*a) * i64::from(*b)),
+            _ if !self.is_real() || !other.is_real() => {
+                match (self.to_exact_complex(), other.to_exact_complex()) {
+                    (Some(a), Some(b)) => Number::from(a.mul(&b)),
+                    _ => Number::from(self.to_complex().mul(&other.to_complex())),
+                }
+            }
+            _ => match (self.to_rational(), other.to_rational()) {
+                (Some(a), Some(b)) => Number::from(a.mul(&b)),
+                _ => Number::Float(self.to_f64() * other.to_f64()),
+            },
+        })
+    }
+
+    /// Division of exact numbers is exact, possibly yielding a fraction.
+    /// Dividing by exact zero is an error, dividing by inexact zero follows
+    /// IEEE 754.
+    pub fn checked_div(&self, other: &Number) -> Result<Number, Error> {
+        if other.is_exact() && other.is_zero() {
+            return Err(division_by_zero());
+        }
+        if !self.is_real() || !other.is_real() {
+            return Ok(match (self.to_exact_complex(), other.to_exact_complex()) {
+                (Some(a), Some(b)) => Number::from(a.div(&b).expect("divisor is not zero")),
+                _ => Number::from(self.to_complex().div(&other.to_complex())),
+            });
+        }
+        match (self.to_rational(), other.to_rational()) {
+            (Some(a), Some(b)) => Ok(Number::from(a.div(&b).expect("divisor is not zero"))),
+            _ => Ok(Number::Float(self.to_f64() / other.to_f64())),
+        }
+    }
+
+    /// Integer division truncated towards zero.
+    pub fn quotient(&self, other: &Number) -> Result<Number, Error> {
+        self.div_rem(other).map(|(quotient, _)| quotient)
+    }
+
+    /// Remainder of `quotient`, it has the sign of the dividend.
+    pub fn remainder(&self, other: &Number) -> Result<Number, Error> {
+        self.div_rem(other).map(|(_, remainder)| remainder)
+    }
+
+    /// Remainder of division rounded towards negative infinity, it has the
+    /// sign of the divisor.
+    pub fn modulo(&self, other: &Number) -> Result<Number, Error> {
+        let (_, remainder) = self.div_rem(other)?;
+        if !remainder.is_zero() && remainder.is_negative() != other.is_negative() {
+            return remainder.checked_add(other);
+        }
+        Ok(remainder)
+    }
+
+    /// Truncated division of integers, floats with integral value are
+    /// accepted and make both results inexact.
+    fn div_rem(&self, other: &Number) -> Result<(Number, Number), Error> {
+        for n in [self, other].iter() {
+            if !n.is_integer() {
+                return Err(Error::Reason(format!("expected integer, got: {}", n)));
+            }
+        }
+        if other.is_zero() {
+            return Err(division_by_zero());
+        }
+
+        Ok(match (self, other) {
+            (Number::Int(a), Number::Int(b)) => {
+                let (a, b) = (i64::from(*a), i64::from(*b));
+                (Number::from(a / b), Number::from(a % b))
+            }
+            _ => match (self.to_big(), other.to_big()) {
+                (Some(a), Some(b)) => {
+                    let (quotient, remainder) = a.div_rem(&b).expect("divisor is not zero");
+                    (Number::from(quotient), Number::from(remainder))
+                }
+                _ => {
+                    let (a, b) = (self.to_f64(), other.to_f64());
+                    (Number::Float((a / b).trunc()), Number::Float(a % b))
+                }
+            },
+        })
+    }
+
+    pub fn abs(&self) -> Result<Number, Error> {
+        Ok(match self.real()? {
+            Number::Int(i) => Number::from(i64::from(*i).abs()),
+            Number::Big(b) => Number::Big(b.abs()),
+            Number::Rational(r) => Number::Rational(r.abs()),
+            Number::Float(f) => Number::Float(f.abs()),
+            Number::Complex(_) | Number::ExactComplex(_) => unreachable!("checked to be real"),
+        })
+    }
+
+    /// Identity of numbers used by `eqv?`: both must have the same exactness
+    /// and the same value. Unlike `==` this is reflexive, so NaN is `eqv?` to
+    /// itself while `0.0` and `-0.0` are distinct.
+    pub fn eqv(&self, other: &Number) -> bool {
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => a == b,
+            (Number::Big(a), Number::Big(b)) => a == b,
+            (Number::Rational(a), Number::Rational(b)) => a == b,
+            (Number::Float(a), Number::Float(b)) => a.to_bits() == b.to_bits(),
+            (Number::Complex(a), Number::Complex(b)) => {
+                a.re.to_bits() == b.re.to_bits() && a.im.to_bits() == b.im.to_bits()
+            }
+            (Number::ExactComplex(a), Number::ExactComplex(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Number::Int(0)
+    }
+
+    pub fn is_positive(&self) -> bool {
+        *self > Number::Int(0)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        *self < Number::Int(0)
+    }
+
+    /// Parity of integer, floats with integral value are accepted.
+    pub fn is_even(&self) -> Result<bool, Error> {
+        match self {
+            Number::Big(b) => Ok(b.is_even()),
+            _ => self.remainder(&Number::Int(2)).map(|r| r.is_zero()),
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        match self {
+            Number::Int(_) | Number::Big(_) => true,
+            Number::Rational(_) | Number::Complex(_) | Number::ExactComplex(_) => false,
+            Number::Float(f) => f.is_finite() && f.fract() == 0.0,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use super::BigInt;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Exact fraction kept in lowest terms with positive denominator, so equal
+/// values have equal representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Rational {
+    /// Normalized `numerator / denominator`, `None` when denominator is zero.
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Option<Rational> {
+        if denominator.is_zero() {
+            return None;
+        }
+        let gcd = numerator.gcd(&denominator);
+        let (mut numerator, _) = numerator.div_rem(&gcd).expect("gcd is not zero");
+        let (mut denominator, _) = denominator.div_rem(&gcd).expect("gcd is not zero");
+        if denominator.is_negative() {
+            numerator = numerator.neg();
+            denominator = denominator.neg();
+        }
+        Some(Rational {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInt::from(1)
+    }
+
+    /// Exact value of finite float, `None` for infinities and NaN.
+    pub fn from_f64(f: f64) -> Option<Rational> {
+        if !f.is_finite() {
+            return None;
+        }
+        // Doubling is exact and a finite float has a bounded number of
+        // fractional bits, so this ends with an integral value.
+        let mut scaled = f;
+        let mut bits = 0;
+        while scaled.fract() != 0.0 {
+            scaled *= 2.0;
+            bits += 1;
+        }
+        Rational::new(
+            BigInt::from_f64(scaled).expect("scaled float is integral"),
+            BigInt::from(1).shl(bits),
+        )
+    }
+
+    /// Nearest float, computed from enough bits of the quotient that it is
+    /// rounded only once.
+    pub fn to_f64(&self) -> f64 {
+        let numerator = self.numerator.abs();
+        // Scales the quotient to have at least 65 significant bits.
+        let shift = self.denominator.bit_length() as i64 - numerator.bit_length() as i64 + 65;
+        let (quotient, remainder) = if shift >= 0 {
+            numerator.shl(shift as usize).div_rem(&self.denominator)
+        } else {
+            numerator.div_rem(&self.denominator.shl(-shift as usize))
+        }
+        .expect("denominator is not zero");
+        // Bits past the quotient only matter for breaking ties, a single
+        // sticky bit stands in for all of them.
+        let (quotient, shift) = if remainder.is_zero() {
+            (quotient, shift)
+        } else {
+            (quotient.shl(1).add(&BigInt::from(1)), shift + 1)
+        };
+
+        let magnitude = scale(quotient.to_f64(), -shift);
+        if self.numerator.is_negative() {
+            return -magnitude;
+        }
+        magnitude
+    }
+
+    pub fn neg(&self) -> Rational {
+        Rational {
+            numerator: self.numerator.neg(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    pub fn abs(&self) -> Rational {
+        Rational {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    pub fn add(&self, other: &Rational) -> Rational {
+        Rational::new(
+            self.numerator
+                .mul(&other.denominator)
+                .add(&other.numerator.mul(&self.denominator)),
+            self.denominator.mul(&other.denominator),
+        )
+        .expect("product of denominators is not zero")
+    }
+
+    pub fn sub(&self, other: &Rational) -> Rational {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Rational) -> Rational {
+        Rational::new(
+            self.numerator.mul(&other.numerator),
+            self.denominator.mul(&other.denominator),
+        )
+        .expect("product of denominators is not zero")
+    }
+
+    pub fn pow(&self, exponent: u64) -> Rational {
+        // Powers of coprime numbers stay coprime, so no normalization needed.
+        Rational {
+            numerator: self.numerator.pow(exponent),
+            denominator: self.denominator.pow(exponent),
+        }
+    }
+
+    /// Quotient of the fractions, `None` when dividing by zero.
+    pub fn div(&self, other: &Rational) -> Option<Rational> {
+        Rational::new(
+            self.numerator.mul(&other.denominator),
+            self.denominator.mul(&other.numerator),
+        )
+    }
+}
+
+impl From<BigInt> for Rational {
+    fn from(numerator: BigInt) -> Rational {
+        Rational {
+            numerator,
+            denominator: BigInt::from(1),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        // Denominators are positive, so cross multiplication keeps the order.
+        self.numerator
+            .mul(&other.denominator)
+            .cmp(&other.numerator.mul(&self.denominator))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+/// Computes `f * 2^exponent`, which is exact unless the result is out of the
+/// normal range.
+fn scale(mut f: f64, mut exponent: i64) -> f64 {
+    while exponent > 1000 {
+        f *= 2f64.powi(1000);
+        exponent -= 1000;
+    }
+    while exponent < -1000 {
+        f *= 2f64.powi(-1000);
+        exponent += 1000;
+    }
+    f * 2f64.powi(exponent as i32)
+}
@@ -2,6 +2,7 @@ use super::lexer::Token;
 use super::number::Number;
 use super::sexp::{Atom, Error, Sexp};
 use std::collections::VecDeque;
+use std::rc::Rc;
 
 pub fn read_from_tokens(tokens: &mut VecDeque<Token>) -> Result<Sexp, Error> {
     let token = tokens.pop_front();
@@ -10,26 +11,26 @@ pub fn read_from_tokens(tokens: &mut VecDeque<Token>) -> Result<Sexp, Error> {
         Some(lex) => match lex {
             Token::LParen => parse_list(tokens),
             Token::RParen => Err(Error::Reason("unexpected ')'".to_owned())),
-            Token::Quote => parse_quoted(tokens),
-            Token::Symbol(s) => Ok(parse_atom(&s)),
-            Token::String(s) => Ok(parse_string(&s)),
+            Token::Quote => parse_quoted("quote", tokens),
+            Token::Quasiquote => parse_quoted("quasiquote", tokens),
+            Token::Unquote => parse_quoted("unquote", tokens),
+            Token::UnquoteSplicing => parse_quoted("unquote-splicing", tokens),
+            Token::Symbol(s) => parse_atom(&s),
+            Token::String(s) => Ok(Sexp::Atom(Atom::String(Rc::from(s)))),
         },
     }
 }
 
 fn parse_list(tokens: &mut VecDeque<Token>) -> Result<Sexp, Error> {
-    fn collect(mut vec: Vec<Sexp>) -> Sexp {
-        let start = Sexp::Cons {
-            car: Box::new(vec.pop().unwrap()),
-            cdr: Box::new(Sexp::Atom(Atom::Nil)),
-        };
-        vec.into_iter().rfold(start, |acc, x| Sexp::Cons {
-            car: Box::new(x),
-            cdr: Box::new(acc),
+    fn collect(vec: Vec<Sexp>, tail: Sexp) -> Sexp {
+        vec.into_iter().rfold(tail, |acc, x| Sexp::Cons {
+            car: Rc::new(x),
+            cdr: Rc::new(acc),
         })
     }
 
     let mut vec: Vec<Sexp> = Vec::new();
+    let mut tail = Sexp::Atom(Atom::Nil);
     let mut terminated = false;
     while let Some(token) = tokens.front() {
         if *token == Token::RParen {
@@ -38,66 +39,70 @@ fn parse_list(tokens: &mut VecDeque<Token>) -> Result<Sexp, Error> {
             break;
         }
 
+        if *token == Token::Symbol(".".to_owned()) && !vec.is_empty() {
+            tokens.pop_front();
+            tail = read_from_tokens(tokens)?;
+            if tokens.pop_front() != Some(Token::RParen) {
+                return Err(Error::Reason(
+                    "expected ')' after dotted pair tail".to_owned(),
+                ));
+            }
+            terminated = true;
+            break;
+        }
+
         let sexp = read_from_tokens(tokens)?;
         vec.push(sexp);
     }
 
     if !terminated {
-        let mut msg = format!("missing ')' in expression: {}", collect(vec));
+        let mut msg = format!("missing ')' in expression: {}", collect(vec, tail));
         msg.pop();
         return Err(Error::Reason(msg));
     }
 
-    Ok(collect(vec))
+    Ok(collect(vec, tail))
 }
 
-fn parse_string(token: &str) -> Sexp {
-    let mut rev = token.chars().rev();
-
-    if let Some(first) = rev.next() {
-        let mut sexp = Box::new(Sexp::Cons {
-            car: Box::new(Sexp::Atom(Atom::Char(first))),
-            cdr: Box::new(Sexp::Atom(Atom::Nil)),
-        });
-
-        for c in rev {
-            let new = Box::new(Sexp::Cons {
-                car: Box::new(Sexp::Atom(Atom::Char(c))),
-                cdr: sexp,
-            });
-
-            sexp = new;
-        }
-
-        return *sexp;
+fn parse_atom(token: &str) -> Result<Sexp, Error> {
+    match token {
+        "true" => Ok(Sexp::Atom(Atom::Bool(true))),
+        "false" => Ok(Sexp::Atom(Atom::Bool(false))),
+        "nil" => Ok(Sexp::Atom(Atom::Nil)),
+        _ if is_numeral(token) => Ok(Sexp::Atom(Atom::Number(Number::parse(token, 10)?))),
+        _ => Ok(Sexp::Atom(Atom::Symbol(token.to_owned()))),
     }
-
-    return Sexp::Atom(Atom::Nil);
 }
 
-fn parse_atom(token: &str) -> Sexp {
-    match token.as_ref() {
-        "true" => Sexp::Atom(Atom::Bool(true)),
-        "false" => Sexp::Atom(Atom::Bool(false)),
-        "nil" => Sexp::Atom(Atom::Nil),
-        _ => match token.parse::<i32>() {
-            Ok(i) => Sexp::Atom(Atom::Number(Number::Int(i))),
-            Err(_) => match token.parse::<f64>() {
-                Ok(f) => Sexp::Atom(Atom::Number(Number::Float(f))),
-                Err(_) => Sexp::Atom(Atom::Symbol(token.to_owned())),
-            },
-        },
+/// Whether `token` is meant to be a number, so that it is an error rather
+/// than a symbol when it can't be read as one. Numerals start with a digit,
+/// a prefix such as `#x`, or a sign or point followed by a digit. Signed
+/// `i`, `inf.0` and `nan.0` are numerals as well.
+fn is_numeral(token: &str) -> bool {
+    let unsigned = token.strip_prefix(['+', '-']).unwrap_or(token);
+    let signed = unsigned.len() < token.len();
+    let mut chars = unsigned.chars();
+    match chars.next() {
+        Some('#') => !signed && matches!(chars.next(), Some(c) if "xXbBoOdDeEiI".contains(c)),
+        Some('.') => matches!(chars.next(), Some(c) if c.is_ascii_digit()),
+        Some(c) if c.is_ascii_digit() => true,
+        _ => {
+            let special = unsigned.to_ascii_lowercase();
+            signed
+                && (special == "i" || special.starts_with("inf.0") || special.starts_with("nan.0"))
+        }
     }
 }
 
-fn parse_quoted(tokens: &mut VecDeque<Token>) -> Result<Sexp, Error> {
+/// Desugars prefix quotation marks into `(form exp)` lists.
+fn parse_quoted(form: &str, tokens: &mut VecDeque<Token>) -> Result<Sexp, Error> {
     let tail = Sexp::Cons {
-        car: Box::new(read_from_tokens(tokens)?),
-        cdr: Box::new(Sexp::Atom(Atom::Nil)),
+        car: Rc::new(read_from_tokens(tokens)?),
+        cdr: Rc::new(Sexp::Atom(Atom::Nil)),
     };
     let sexp = Sexp::Cons {
-        car: Box::new(Sexp::Atom(Atom::Symbol("'".to_owned()))),
-        cdr: Box::new(tail),
+        car: Rc::new(Sexp::Atom(Atom::Symbol(form.to_owned()))),
+        cdr: Rc::new(tail),
     };
     Ok(sexp)
 }
@@ -1,52 +1,159 @@
+use super::env::Env;
 use super::number::Number;
+use super::syntax_rules::SyntaxRules;
 use std::fmt;
 use std::iter::{IntoIterator, Iterator};
+use std::mem;
 use std::rc::Rc;
 
+/// Separates name of a symbol renamed by hygienic macro expansion from ids of
+/// the scope the macro was defined in and of the expansion that introduced it.
+pub const RENAME_MARKER: char = '\u{1}';
+
+/// Renames `symbol` introduced by expansion `expansion` of a macro defined
+/// in scope `scope`.
+pub fn rename(symbol: &str, scope: usize, expansion: usize) -> String {
+    format!("{}{}{}.{}", symbol, RENAME_MARKER, scope, expansion)
+}
+
+/// Name of `symbol` as written in the source, with all renames stripped.
+pub fn original_name(symbol: &str) -> &str {
+    match symbol.find(RENAME_MARKER) {
+        Some(i) => &symbol[..i],
+        None => symbol,
+    }
+}
+
+/// Splits the most recent rename off `symbol` into the name it had before and
+/// id of the scope the renaming macro was defined in, if it was renamed at
+/// all.
+pub fn strip_rename(symbol: &str) -> Option<(&str, usize)> {
+    let i = symbol.rfind(RENAME_MARKER)?;
+    let (scope, _) = symbol[i + RENAME_MARKER.len_utf8()..].split_once('.')?;
+    Some((&symbol[..i], scope.parse().ok()?))
+}
+
 #[derive(Clone)]
 pub enum Atom {
     Nil,
     Char(char),
     Bool(bool),
+    /// Immutable UTF-8 text, cloning the atom shares the text.
+    String(Rc<str>),
     Symbol(String),
     Number(Number),
     Func {
         fun: fn(&Sexp) -> Result<Sexp, Error>,
         name: &'static str,
     },
-    // Lambda(Lambda)
+    /// Builtin that additionally receives environment of the call site.
+    EnvFunc {
+        fun: fn(&Sexp, &Env) -> Result<Sexp, Error>,
+        name: &'static str,
+    },
+    Lambda(Lambda),
+    /// Transformer bound by `defmacro`, it receives arguments unevaluated and
+    /// returns the expression to evaluate in their place.
+    Macro(Lambda),
+    /// Pattern based transformer created by `syntax-rules`.
+    Syntax(Rc<SyntaxRules>),
 }
 
 impl fmt::Display for Atom {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match &*self {
+        match self {
             Atom::Nil => write!(f, "nil"),
             Atom::Char(c) => write!(f, "{}", c),
             Atom::Bool(b) => write!(f, "{}", b),
-            Atom::Symbol(s) => write!(f, "{}", s),
+            Atom::String(s) => write_string(f, s),
+            Atom::Symbol(s) => write!(f, "{}", original_name(s)),
             Atom::Number(n) => write!(f, "{}", n),
             Atom::Func { name, .. } => write!(f, "builtin function {}", name),
+            Atom::EnvFunc { name, .. } => write!(f, "builtin function {}", name),
+            Atom::Lambda(_) => write!(f, "lambda function"),
+            Atom::Macro(_) | Atom::Syntax(_) => write!(f, "macro"),
+        }
+    }
+}
+
+/// Writes `s` in double quotes, escaping characters the lexer couldn't read
+/// back literally.
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\t' => write!(f, "\\t")?,
+            '\r' => write!(f, "\\r")?,
+            '\0' => write!(f, "\\0")?,
+            c if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32)?,
+            c => write!(f, "{}", c)?,
         }
     }
+    write!(f, "\"")
 }
 
 impl fmt::Debug for Atom {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &*self {
+        match self {
             Atom::Nil => write!(f, "nil"),
             Atom::Char(c) => write!(f, "{}", c),
             Atom::Bool(b) => write!(f, "{}", b),
-            Atom::Symbol(s) => write!(f, "{}", s),
+            Atom::String(s) => write_string(f, s),
+            Atom::Symbol(s) => write!(f, "{}", s.replace(RENAME_MARKER, "#")),
             Atom::Number(n) => write!(f, "{}", n),
             Atom::Func { name, .. } => write!(f, "builtin function {}", name),
+            Atom::EnvFunc { name, .. } => write!(f, "builtin function {}", name),
+            Atom::Lambda(_) => write!(f, "lambda function"),
+            Atom::Macro(_) | Atom::Syntax(_) => write!(f, "macro"),
         }
     }
 }
 
+/// Atoms are equal when `eqv?` holds for them. Numbers must agree in
+/// exactness as well as value, builtins are compared by name and strings,
+/// lambdas and macros by identity, so only clones of the same value are
+/// equal.
+impl PartialEq for Atom {
+    fn eq(&self, other: &Atom) -> bool {
+        match (self, other) {
+            (Atom::Nil, Atom::Nil) => true,
+            (Atom::Char(a), Atom::Char(b)) => a == b,
+            (Atom::Bool(a), Atom::Bool(b)) => a == b,
+            (Atom::String(a), Atom::String(b)) => Rc::ptr_eq(a, b),
+            (Atom::Symbol(a), Atom::Symbol(b)) => a == b,
+            (Atom::Number(a), Atom::Number(b)) => a.eqv(b),
+            (Atom::Func { name: a, .. }, Atom::Func { name: b, .. }) => a == b,
+            (Atom::EnvFunc { name: a, .. }, Atom::EnvFunc { name: b, .. }) => a == b,
+            (Atom::Lambda(a), Atom::Lambda(b)) => a.same_closure(b),
+            (Atom::Macro(a), Atom::Macro(b)) => a.same_closure(b),
+            (Atom::Syntax(a), Atom::Syntax(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// User defined function created by the `lambda` special form.
+///
+/// `env` is the environment the lambda was created in, the body is evaluated
+/// in a fresh child of it, so free symbols resolve lexically.
 #[derive(Clone)]
 pub struct Lambda {
     pub params_exp: Rc<Sexp>,
     pub body_exp: Rc<Sexp>,
+    pub env: Env,
+}
+
+impl Lambda {
+    /// Whether `self` and `other` are clones of the closure created by the
+    /// same evaluation of `lambda`.
+    pub fn same_closure(&self, other: &Lambda) -> bool {
+        Rc::ptr_eq(&self.params_exp, &other.params_exp)
+            && Rc::ptr_eq(&self.body_exp, &other.body_exp)
+            && self.env.ptr_eq(&other.env)
+    }
 }
 
 #[derive(Debug)]
@@ -56,29 +163,170 @@ pub enum Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let Error::Reason(res) = &*self;
+        let Error::Reason(res) = self;
         write!(f, "Error: {}", res)
     }
 }
 
+/// Conses share their cars and cdrs, cloning a sexp is constant time, so
+/// evaluation can hand subexpressions around by value.
 #[derive(Clone)]
 pub enum Sexp {
     Atom(Atom),
-    Cons { car: Box<Sexp>, cdr: Box<Sexp> },
+    Cons { car: Rc<Sexp>, cdr: Rc<Sexp> },
+}
+
+impl Sexp {
+    /// Builds a proper list out of `items`.
+    pub fn list(items: Vec<Sexp>) -> Sexp {
+        items
+            .into_iter()
+            .rfold(Sexp::Atom(Atom::Nil), |acc, x| Sexp::Cons {
+                car: Rc::new(x),
+                cdr: Rc::new(acc),
+            })
+    }
+
+    /// Whether the sexp is a proper list, a chain of conses ending in `nil`.
+    /// Conses are immutable, so a list can never contain itself and the walk
+    /// always terminates.
+    pub fn is_list(&self) -> bool {
+        let mut rest = self;
+        while let Sexp::Cons { cdr, .. } = rest {
+            rest = cdr;
+        }
+        rest.is_nil()
+    }
+
+    /// Human readable form of the sexp, unlike `Display`, which writes strings
+    /// quoted so they can be read back.
+    pub fn display(&self) -> Displayed<'_> {
+        Displayed(self)
+    }
+
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Sexp::Atom(Atom::Nil))
+    }
+
+    /// Truthiness used by conditional forms: `false` and `nil` (which is also
+    /// the empty list) are false, every other value, including `0`, is true.
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Sexp::Atom(Atom::Bool(false)) | Sexp::Atom(Atom::Nil))
+    }
+}
+
+/// Frees the cdrs of a list in a loop, dropping a long list recursively would
+/// exhaust the stack.
+impl Drop for Sexp {
+    fn drop(&mut self) {
+        let mut rest = match unlink(self) {
+            Some(rest) => rest,
+            None => return,
+        };
+        // Every unwrapped cons gives up its cdr before it's dropped, so its
+        // own drop doesn't recurse.
+        while let Ok(mut sexp) = Rc::try_unwrap(rest) {
+            rest = match unlink(&mut sexp) {
+                Some(rest) => rest,
+                None => return,
+            };
+        }
+    }
+}
+
+/// Takes the cdr out of `sexp` when it's a cons nothing else refers to.
+fn unlink(sexp: &mut Sexp) -> Option<Rc<Sexp>> {
+    match sexp {
+        Sexp::Cons { cdr, .. }
+            if Rc::strong_count(cdr) == 1 && matches!(**cdr, Sexp::Cons { .. }) =>
+        {
+            Some(mem::replace(cdr, Rc::new(Sexp::Atom(Atom::Nil))))
+        }
+        _ => None,
+    }
+}
+
+/// Writes `sexp` in dotted form, walking the cdrs in a loop so long lists
+/// don't exhaust the stack. `item` writes the cars and the final cdr.
+fn write_dotted(
+    f: &mut fmt::Formatter<'_>,
+    sexp: &Sexp,
+    item: fn(&Sexp, &mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    let mut rest = sexp;
+    let mut depth = 0;
+    while let Sexp::Cons { car, cdr } = rest {
+        write!(f, "(")?;
+        item(car, f)?;
+        write!(f, " . ")?;
+        rest = cdr;
+        depth += 1;
+    }
+    item(rest, f)?;
+    for _ in 0..depth {
+        write!(f, ")")?;
+    }
+    Ok(())
+}
+
+/// Structural equality used by `equal?`: conses are equal when their cars and
+/// cdrs are, strings when they hold the same text and other atoms when they
+/// are `eqv?`.
+impl PartialEq for Sexp {
+    fn eq(&self, other: &Sexp) -> bool {
+        let (mut left, mut right) = (self, other);
+        // Walks down the cdrs in a loop, so long lists don't exhaust the stack.
+        loop {
+            match (left, right) {
+                (Sexp::Atom(Atom::String(a)), Sexp::Atom(Atom::String(b))) => return a == b,
+                (Sexp::Atom(a), Sexp::Atom(b)) => return a == b,
+                (
+                    Sexp::Cons { car, cdr },
+                    Sexp::Cons {
+                        car: other_car,
+                        cdr: other_cdr,
+                    },
+                ) => {
+                    if car != other_car {
+                        return false;
+                    }
+                    left = cdr;
+                    right = other_cdr;
+                }
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// Printed form of a sexp used by `display`, strings inside it are written
+/// as plain text.
+pub struct Displayed<'a>(&'a Sexp);
+
+impl fmt::Display for Displayed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Sexp::Atom(Atom::String(s)) => write!(f, "{}", s),
+            Sexp::Atom(a) => write!(f, "{}", a),
+            Sexp::Cons { .. } => {
+                write_dotted(f, self.0, |item, f| write!(f, "{}", Displayed(item)))
+            }
+        }
+    }
 }
 
 impl fmt::Display for Sexp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &*self {
+        match self {
             Sexp::Atom(a) => write!(f, "{}", a),
-            Sexp::Cons { car, cdr } => write!(f, "({} . {})", car, cdr),
+            Sexp::Cons { .. } => write_dotted(f, self, |item, f| write!(f, "{}", item)),
         }
     }
 }
 
 impl fmt::Debug for Sexp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &*self {
+        match self {
             Sexp::Atom(a) => write!(f, "{}", a),
             Sexp::Cons { car, cdr } => f
                 .debug_struct("Cons")
@@ -112,6 +360,10 @@ impl<'a> Iterator for SexpIntoIterator<'a> {
     fn next(&mut self) -> Option<&'a Sexp> {
         if let Some(i) = self.item {
             match i {
+                Sexp::Atom(Atom::Nil) => {
+                    self.item = None;
+                    return None;
+                }
                 a @ Sexp::Atom(_) => {
                     self.item = None;
                     return Some(a);
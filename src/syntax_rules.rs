@@ -0,0 +1,381 @@
+use super::env::{special_form, Env};
+use super::sexp::{original_name, rename, Atom, Error, Sexp};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Hygienic transformer created by `(syntax-rules (literals...) rules...)`.
+///
+/// Every rule is a `(pattern template)` pair. Use of the macro is matched
+/// against patterns in order, and the template of the first matching rule is
+/// instantiated with pattern variables replaced by the matched input.
+/// Symbols introduced by the template are renamed, so bindings it creates
+/// can't capture symbols of the macro user, and free ones resolve in `env`
+/// the macro was defined in.
+pub struct SyntaxRules {
+    env: Env,
+    scope: usize,
+    ellipsis: String,
+    literals: Vec<String>,
+    rules: Vec<(Sexp, Sexp)>,
+}
+
+#[derive(Clone)]
+enum Binding {
+    One(Sexp),
+    Many(Vec<Binding>),
+}
+
+type Bindings = HashMap<String, Binding>;
+
+/// Whether part of a template is code or quoted data. Quoted data is
+/// substituted, but not renamed, unquoted parts of a quasiquote are code
+/// again.
+#[derive(Clone, Copy, PartialEq)]
+enum Quoting {
+    Code,
+    Quoted,
+    Quasiquoted(usize),
+}
+
+impl Quoting {
+    /// Quoting inside of a list starting with symbol `keyword`.
+    fn enter(self, keyword: &str) -> Quoting {
+        match (self, keyword) {
+            (Quoting::Code, "quote") => Quoting::Quoted,
+            (Quoting::Code, "quasiquote") => Quoting::Quasiquoted(1),
+            (Quoting::Quasiquoted(depth), "quasiquote") => Quoting::Quasiquoted(depth + 1),
+            (Quoting::Quasiquoted(1), "unquote" | "unquote-splicing") => Quoting::Code,
+            (Quoting::Quasiquoted(depth), "unquote" | "unquote-splicing") => {
+                Quoting::Quasiquoted(depth - 1)
+            }
+            (quoting, _) => quoting,
+        }
+    }
+}
+
+impl SyntaxRules {
+    /// Parses arguments of `syntax-rules`, optionally preceded by a custom
+    /// ellipsis symbol as in `(syntax-rules ::: (literals...) rules...)`.
+    pub fn parse(spec: &Sexp, env: &Env) -> Result<SyntaxRules, Error> {
+        let mut args: Vec<&Sexp> = spec.into_iter().collect();
+        let mut ellipsis = "...".to_owned();
+        if let Some(Sexp::Atom(Atom::Symbol(custom))) = args.first() {
+            ellipsis = custom.clone();
+            args.remove(0);
+        }
+        if args.is_empty() {
+            return Err(Error::Reason(
+                "syntax-rules takes a literal list and rules".to_owned(),
+            ));
+        }
+
+        let mut literals = Vec::new();
+        for literal in args[0].into_iter() {
+            match literal {
+                Sexp::Atom(Atom::Symbol(s)) => literals.push(s.clone()),
+                _ => {
+                    return Err(Error::Reason(format!(
+                        "syntax-rules literal must be a symbol, got: {}",
+                        literal
+                    )))
+                }
+            }
+        }
+
+        let mut rules = Vec::new();
+        for rule in args[1..].iter() {
+            let parts: Vec<&Sexp> = rule.into_iter().collect();
+            match (rule, parts.as_slice()) {
+                (Sexp::Cons { .. }, [pattern @ Sexp::Cons { .. }, template]) => {
+                    rules.push(((*pattern).clone(), (*template).clone()))
+                }
+                _ => {
+                    return Err(Error::Reason(format!(
+                        "malformed syntax-rules rule: {}",
+                        rule
+                    )))
+                }
+            }
+        }
+
+        Ok(SyntaxRules {
+            env: env.clone(),
+            scope: env.define_scope(),
+            ellipsis,
+            literals,
+            rules,
+        })
+    }
+
+    /// Transforms arguments `args` of the macro use into expression to
+    /// evaluate in place of it.
+    pub fn expand(&self, args: &Sexp) -> Result<Sexp, Error> {
+        for (pattern, template) in self.rules.iter() {
+            // The keyword position of the pattern is ignored.
+            let pattern = match pattern {
+                Sexp::Cons { cdr, .. } => cdr,
+                _ => continue,
+            };
+
+            let mut bindings = Bindings::new();
+            if self.matches(pattern, args, &mut bindings) {
+                let id = self.env.next_expansion();
+                return self.instantiate(template, &bindings, id, Quoting::Code);
+            }
+        }
+
+        Err(Error::Reason(format!("no syntax rule matches: {}", args)))
+    }
+
+    fn is_ellipsis(&self, sexp: &Sexp) -> bool {
+        matches!(sexp, Sexp::Atom(Atom::Symbol(s)) if *s == self.ellipsis)
+    }
+
+    fn matches(&self, pattern: &Sexp, input: &Sexp, bindings: &mut Bindings) -> bool {
+        match pattern {
+            Sexp::Atom(Atom::Symbol(s)) if s == "_" => true,
+            Sexp::Atom(Atom::Symbol(s)) if self.literals.contains(s) => {
+                matches!(input, Sexp::Atom(Atom::Symbol(i)) if original_name(i) == s)
+            }
+            Sexp::Atom(Atom::Symbol(s)) => {
+                bindings.insert(s.clone(), Binding::One(input.clone()));
+                true
+            }
+            Sexp::Atom(_) => pattern == input,
+            Sexp::Cons { .. } => {
+                let (items, tail) = split(pattern);
+                match items.iter().position(|item| self.is_ellipsis(item)) {
+                    Some(i) if i > 0 => self.matches_ellipsis(&items, i, tail, input, bindings),
+                    _ => self.matches_pair(pattern, input, bindings),
+                }
+            }
+        }
+    }
+
+    fn matches_pair(&self, pattern: &Sexp, input: &Sexp, bindings: &mut Bindings) -> bool {
+        match (pattern, input) {
+            (
+                Sexp::Cons { car, cdr },
+                Sexp::Cons {
+                    car: input_car,
+                    cdr: input_cdr,
+                },
+            ) => self.matches(car, input_car, bindings) && self.matches(cdr, input_cdr, bindings),
+            _ => false,
+        }
+    }
+
+    /// Matches pattern `before... repeated ellipsis after... . tail`, where
+    /// `repeated` is at `items[ellipsis - 1]`.
+    fn matches_ellipsis(
+        &self,
+        items: &[&Sexp],
+        ellipsis: usize,
+        tail: &Sexp,
+        input: &Sexp,
+        bindings: &mut Bindings,
+    ) -> bool {
+        let before = &items[..ellipsis - 1];
+        let repeated = items[ellipsis - 1];
+        let after = &items[ellipsis + 1..];
+        let (inputs, input_tail) = split(input);
+        if inputs.len() < before.len() + after.len() {
+            return false;
+        }
+
+        let repeats = inputs.len() - before.len() - after.len();
+        let (head, rest) = inputs.split_at(before.len());
+        let (middle, end) = rest.split_at(repeats);
+
+        for (pattern, input) in before.iter().zip(head) {
+            if !self.matches(pattern, input, bindings) {
+                return false;
+            }
+        }
+
+        let mut matched: Vec<Bindings> = Vec::new();
+        for input in middle {
+            let mut local = Bindings::new();
+            if !self.matches(repeated, input, &mut local) {
+                return false;
+            }
+            matched.push(local);
+        }
+        for var in self.pattern_vars(repeated) {
+            let values = matched
+                .iter_mut()
+                .map(|local| local.remove(&var).expect("pattern variable bound by match"))
+                .collect();
+            bindings.insert(var, Binding::Many(values));
+        }
+
+        for (pattern, input) in after.iter().zip(end) {
+            if !self.matches(pattern, input, bindings) {
+                return false;
+            }
+        }
+        self.matches(tail, input_tail, bindings)
+    }
+
+    fn pattern_vars(&self, pattern: &Sexp) -> Vec<String> {
+        match pattern {
+            Sexp::Atom(Atom::Symbol(s))
+                if s != "_" && *s != self.ellipsis && !self.literals.contains(s) =>
+            {
+                vec![s.clone()]
+            }
+            Sexp::Atom(_) => Vec::new(),
+            Sexp::Cons { car, cdr } => {
+                let mut vars = self.pattern_vars(car);
+                vars.extend(self.pattern_vars(cdr));
+                vars
+            }
+        }
+    }
+
+    /// Builds expression out of `template`. Symbols that aren't pattern
+    /// variables are renamed with expansion `id` unless they are quoted.
+    fn instantiate(
+        &self,
+        template: &Sexp,
+        bindings: &Bindings,
+        id: usize,
+        quoting: Quoting,
+    ) -> Result<Sexp, Error> {
+        match template {
+            Sexp::Atom(Atom::Symbol(s)) => match bindings.get(s) {
+                Some(Binding::One(value)) => Ok(value.clone()),
+                Some(Binding::Many(_)) => Err(Error::Reason(format!(
+                    "pattern variable used without ellipsis: {}",
+                    s
+                ))),
+                None if quoting == Quoting::Code && special_form(s).is_none() => {
+                    Ok(Sexp::Atom(Atom::Symbol(rename(s, self.scope, id))))
+                }
+                None => Ok(template.clone()),
+            },
+            Sexp::Atom(_) => Ok(template.clone()),
+            Sexp::Cons { car, cdr } => {
+                // `(... template)` escapes ellipses inside of `template`.
+                if self.is_ellipsis(car) {
+                    if let Sexp::Cons {
+                        car: escaped,
+                        cdr: rest,
+                    } = &**cdr
+                    {
+                        if rest.is_nil() {
+                            let literal = SyntaxRules {
+                                env: self.env.clone(),
+                                scope: self.scope,
+                                ellipsis: String::new(),
+                                literals: Vec::new(),
+                                rules: Vec::new(),
+                            };
+                            return literal.instantiate(escaped, bindings, id, quoting);
+                        }
+                    }
+                }
+                let quoting = match &**car {
+                    Sexp::Atom(Atom::Symbol(s)) => quoting.enter(s),
+                    _ => quoting,
+                };
+
+                let (items, tail) = split(template);
+                let mut expanded = Vec::new();
+                let mut i = 0;
+                while i < items.len() {
+                    let mut depth = 0;
+                    while i + depth + 1 < items.len() && self.is_ellipsis(items[i + depth + 1]) {
+                        depth += 1;
+                    }
+                    if depth == 0 {
+                        expanded.push(self.instantiate(items[i], bindings, id, quoting)?);
+                    } else {
+                        self.instantiate_many(
+                            items[i],
+                            bindings,
+                            depth,
+                            id,
+                            quoting,
+                            &mut expanded,
+                        )?;
+                    }
+                    i += depth + 1;
+                }
+
+                let tail = self.instantiate(tail, bindings, id, quoting)?;
+                Ok(expanded.into_iter().rfold(tail, |acc, x| Sexp::Cons {
+                    car: Rc::new(x),
+                    cdr: Rc::new(acc),
+                }))
+            }
+        }
+    }
+
+    /// Instantiates `template` followed by `depth` ellipses once for every
+    /// element of the pattern variables it uses.
+    fn instantiate_many(
+        &self,
+        template: &Sexp,
+        bindings: &Bindings,
+        depth: usize,
+        id: usize,
+        quoting: Quoting,
+        expanded: &mut Vec<Sexp>,
+    ) -> Result<(), Error> {
+        if depth == 0 {
+            expanded.push(self.instantiate(template, bindings, id, quoting)?);
+            return Ok(());
+        }
+
+        let vars: Vec<&String> = self
+            .pattern_vars(template)
+            .iter()
+            .filter_map(|var| bindings.get_key_value(var))
+            .filter(|(_, binding)| matches!(binding, Binding::Many(_)))
+            .map(|(var, _)| var)
+            .collect();
+        if vars.is_empty() {
+            return Err(Error::Reason(format!(
+                "no pattern variable to repeat in template: {}",
+                template
+            )));
+        }
+
+        let lengths: Vec<usize> = vars
+            .iter()
+            .map(|var| match &bindings[*var] {
+                Binding::Many(values) => values.len(),
+                Binding::One(_) => 0,
+            })
+            .collect();
+        if lengths.iter().any(|len| *len != lengths[0]) {
+            return Err(Error::Reason(format!(
+                "pattern variables repeat different number of times in template: {}",
+                template
+            )));
+        }
+
+        for i in 0..lengths[0] {
+            let mut local = bindings.clone();
+            for var in vars.iter() {
+                if let Binding::Many(values) = &bindings[*var] {
+                    local.insert((*var).clone(), values[i].clone());
+                }
+            }
+            self.instantiate_many(template, &local, depth - 1, id, quoting, expanded)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits possibly improper list into its items and its final cdr.
+fn split(list: &Sexp) -> (Vec<&Sexp>, &Sexp) {
+    let mut items = Vec::new();
+    let mut rest = list;
+    while let Sexp::Cons { car, cdr } = rest {
+        items.push(&**car);
+        rest = cdr;
+    }
+    (items, rest)
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{run, run_str};
+
+#[test]
+fn variadic_addition_and_multiplication() {
+    assert_eq!(run_str("(+)"), "0");
+    assert_eq!(run_str("(+ 1 2 3 4)"), "10");
+    assert_eq!(run_str("(*)"), "1");
+    assert_eq!(run_str("(* 2 3 4)"), "24");
+}
+
+#[test]
+fn subtraction_and_negation() {
+    assert_eq!(run_str("(- 5)"), "-5");
+    assert_eq!(run_str("(- 10 1 2 3)"), "4");
+    assert!(run("(-)").is_err());
+}
+
+#[test]
+fn division() {
+    assert_eq!(run_str("(/ 12 2 3)"), "2");
+    assert_eq!(run_str("(int? (/ 6 3))"), "true");
+    assert_eq!(run_str("(/ 1 2)"), "1/2");
+    assert_eq!(run_str("(/ 1.0 2)"), "0.5");
+    assert_eq!(run_str("(/ 4)"), "1/4");
+    assert!(run("(/)").is_err());
+}
+
+#[test]
+fn float_contagion() {
+    assert_eq!(run_str("(+ 1 2.5)"), "3.5");
+    assert_eq!(run_str("(float? (+ 1 2.0))"), "true");
+    assert_eq!(run_str("(float? (* 2 1.5 2))"), "true");
+    assert_eq!(run_str("(int? (+ 1 2))"), "true");
+}
+
+#[test]
+fn integer_overflow_promotes() {
+    assert_eq!(run_str("(+ 2147483647 1)"), "2147483648");
+    assert_eq!(run_str("(- -2147483647 2)"), "-2147483649");
+    assert_eq!(run_str("(* 65536 65536)"), "4294967296");
+    assert_eq!(run_str("(abs (- -2147483647 1))"), "2147483648");
+    assert_eq!(run_str("(/ (- -2147483647 1) -1)"), "2147483648");
+}
+
+#[test]
+fn division_by_zero_is_an_error() {
+    assert_eq!(run_str("(/ 1 0)"), "Error: division by zero");
+    assert_eq!(run_str("(/ 1.5 0)"), "Error: division by zero");
+    assert_eq!(run_str("(/ 0)"), "Error: division by zero");
+    assert_eq!(run_str("(quotient 1 0)"), "Error: division by zero");
+    assert_eq!(run_str("(remainder 1 0)"), "Error: division by zero");
+    assert_eq!(run_str("(modulo 1 0.0)"), "Error: division by zero");
+    assert_eq!(run_str("(/ 1 0.0)"), "+inf.0");
+}
+
+#[test]
+fn integer_division() {
+    assert_eq!(run_str("(quotient 17 5)"), "3");
+    assert_eq!(run_str("(quotient -17 5)"), "-3");
+    assert_eq!(run_str("(remainder 17 -5)"), "2");
+    assert_eq!(run_str("(remainder -17 5)"), "-2");
+    assert_eq!(run_str("(modulo 17 -5)"), "-3");
+    assert_eq!(run_str("(modulo -17 5)"), "3");
+    assert_eq!(run_str("(modulo -15 5)"), "0");
+    assert_eq!(run_str("(float? (modulo -7 2.0))"), "true");
+    assert_eq!(run_str("(modulo -7 2.0)"), "1.0");
+    assert_eq!(
+        run_str("(quotient 7 2.5)"),
+        "Error: expected integer, got: 2.5"
+    );
+}
+
+#[test]
+fn abs_min_max() {
+    assert_eq!(run_str("(abs -7)"), "7");
+    assert_eq!(run_str("(abs -7.5)"), "7.5");
+    assert_eq!(run_str("(min 3 1 2)"), "1");
+    assert_eq!(run_str("(max 3 1 2)"), "3");
+    assert_eq!(run_str("(float? (max 3 1 2.0))"), "true");
+    assert!(run("(max)").is_err());
+}
+
+#[test]
+fn non_numeric_argument() {
+    assert_eq!(run_str("(+ 1 'a)"), "Error: expected number, got: a");
+    assert_eq!(run_str("(abs 1 2)"), "Error: function takes 1 argument");
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::run_str;
+use plib::number::BigInt;
+
+#[test]
+fn big_literals() {
+    assert_eq!(run_str("3000000000"), "3000000000");
+    assert_eq!(run_str("(int? 3000000000)"), "true");
+    assert_eq!(
+        run_str("-123456789012345678901234567890"),
+        "-123456789012345678901234567890"
+    );
+    assert_eq!(run_str("(float? 1e20)"), "true");
+}
+
+#[test]
+fn factorial() {
+    let code = "(define (fact n) (if (= n 0) 1 (* n (fact (- n 1))))) (fact 30)";
+    assert_eq!(run_str(code), "265252859812191058636308480000000");
+}
+
+#[test]
+fn demotion() {
+    assert_eq!(run_str("(- 3000000000 2999999999)"), "1");
+    assert_eq!(run_str("(eqv? (- 3000000000 2999999999) 1)"), "true");
+    assert_eq!(
+        run_str("(/ 265252859812191058636308480000000 265252859812191058636308480000000)"),
+        "1"
+    );
+}
+
+#[test]
+fn big_division() {
+    assert_eq!(
+        run_str("(/ 1000000000000000000000 1000000000000)"),
+        "1000000000"
+    );
+    assert_eq!(
+        run_str("(quotient 100000000000000000000 -7)"),
+        "-14285714285714285714"
+    );
+    assert_eq!(run_str("(remainder 100000000000000000000 -7)"), "2");
+    assert_eq!(run_str("(modulo 100000000000000000000 -7)"), "-5");
+    assert_eq!(run_str("(modulo -100000000000000000000 7)"), "5");
+    assert_eq!(run_str("(/ 10000000000 0)"), "Error: division by zero");
+    assert_eq!(run_str("(/ 10000000001 2)"), "10000000001/2");
+}
+
+#[test]
+fn big_comparisons() {
+    assert_eq!(run_str("(< 1 3000000000 30000000000)"), "true");
+    assert_eq!(run_str("(> -3000000000 -2)"), "false");
+    assert_eq!(run_str("(= 3000000000 3000000000.0)"), "true");
+    assert_eq!(run_str("(< 9007199254740993 9007199254740992.0)"), "false");
+    assert_eq!(run_str("(> 9007199254740993 9007199254740992.0)"), "true");
+    assert_eq!(run_str("(even? 3000000000)"), "true");
+    assert_eq!(run_str("(odd? 3000000001)"), "true");
+    assert_eq!(run_str("(max 1 3000000000)"), "3000000000");
+}
+
+#[test]
+fn float_contagion() {
+    assert_eq!(run_str("(+ 3000000000 0.5)"), "3000000000.5");
+}
+
+/// Deterministic pseudo-random numbers for the property tests.
+fn numbers() -> Vec<BigInt> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut result = Vec::new();
+    for len in 1..40 {
+        let mut digits = String::new();
+        for _ in 0..len {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            digits.push_str(&(state % 1_000_000_000).to_string());
+        }
+        if state.is_multiple_of(3) {
+            digits.insert(0, '-');
+        }
+        result.push(BigInt::parse(&digits, 10).unwrap());
+    }
+    result
+}
+
+#[test]
+fn division_identity() {
+    let numbers = numbers();
+    for a in numbers.iter() {
+        for b in numbers.iter() {
+            let (q, r) = a.div_rem(b).unwrap();
+            assert_eq!(q.mul(b).add(&r), *a, "{} / {}", a, b);
+            assert!(r.abs() < b.abs());
+            assert!(r.is_zero() || r.is_negative() == a.is_negative());
+        }
+    }
+}
+
+#[test]
+fn radix_round_trip() {
+    for n in numbers() {
+        for radix in [2, 7, 10, 16, 36].iter() {
+            let text = n.to_str_radix(*radix);
+            assert_eq!(BigInt::parse(&text, *radix), Some(n.clone()));
+        }
+    }
+    assert_eq!(BigInt::from(-255).to_str_radix(16), "-ff");
+}
+
+#[test]
+fn float_conversion() {
+    assert_eq!(
+        BigInt::from_f64(1e20).unwrap().to_string(),
+        "100000000000000000000"
+    );
+    assert_eq!(BigInt::from_f64(-3.0).unwrap(), BigInt::from(-3));
+    assert_eq!(BigInt::from_f64(0.5), None);
+    assert_eq!(
+        BigInt::parse("100000000000000000000", 10).unwrap().to_f64(),
+        1e20
+    );
+}
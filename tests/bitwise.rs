@@ -0,0 +1,108 @@
+mod common;
+
+use common::{run, run_str};
+
+#[test]
+fn logical_operations() {
+    assert_eq!(run_str("(bitwise-and 12 10)"), "8");
+    assert_eq!(run_str("(bitwise-or 12 10)"), "14");
+    assert_eq!(run_str("(bitwise-xor 12 10)"), "6");
+    assert_eq!(run_str("(bitwise-and 255 -16)"), "240");
+    assert_eq!(run_str("(bitwise-or -8 3)"), "-5");
+    assert_eq!(run_str("(bitwise-and 7 6 12)"), "4");
+    assert_eq!(run_str("(bitwise-and)"), "-1");
+    assert_eq!(run_str("(bitwise-or)"), "0");
+    assert_eq!(run_str("(bitwise-not 0)"), "-1");
+    assert_eq!(run_str("(bitwise-not -6)"), "5");
+}
+
+#[test]
+fn bignum_operands() {
+    assert_eq!(
+        run_str("(bitwise-and 340282366920938463463374607431768211455 4294967296)"),
+        "4294967296"
+    );
+    assert_eq!(
+        run_str("(bitwise-xor -18446744073709551616 18446744073709551615)"),
+        "-1"
+    );
+    assert_eq!(
+        run_str("(bitwise-not 18446744073709551616)"),
+        "-18446744073709551617"
+    );
+    assert_eq!(
+        run_str("(bitwise-and -18446744073709551617 -1)"),
+        "-18446744073709551617"
+    );
+    assert_eq!(
+        run_str("(int? (bitwise-and 18446744073709551616 255))"),
+        "true"
+    );
+}
+
+#[test]
+fn shifts() {
+    assert_eq!(run_str("(arithmetic-shift 1 10)"), "1024");
+    assert_eq!(run_str("(arithmetic-shift 1 64)"), "18446744073709551616");
+    assert_eq!(run_str("(arithmetic-shift 1024 -3)"), "128");
+    assert_eq!(run_str("(arithmetic-shift -5 -1)"), "-3");
+    assert_eq!(run_str("(arithmetic-shift -1 -100)"), "-1");
+    assert_eq!(run_str("(arithmetic-shift 18446744073709551616 -64)"), "1");
+    assert!(run("(arithmetic-shift 1 99999999999)").is_err());
+    assert_eq!(run_str("(arithmetic-shift 5 -100000000000000000000)"), "0");
+    assert_eq!(
+        run_str("(arithmetic-shift -5 -100000000000000000000)"),
+        "-1"
+    );
+    assert_eq!(
+        run_str("(arithmetic-shift 18446744073709551616 -99999999999)"),
+        "0"
+    );
+}
+
+#[test]
+fn bit_counting_and_fields() {
+    assert_eq!(run_str("(bit-count 0)"), "0");
+    assert_eq!(run_str("(bit-count 255)"), "8");
+    assert_eq!(run_str("(bit-count -1)"), "0");
+    assert_eq!(run_str("(bit-count -256)"), "8");
+    assert_eq!(run_str("(bit-count 18446744073709551615)"), "64");
+    assert_eq!(run_str("(bit-field 43981 4 12)"), "188");
+    assert_eq!(run_str("(bit-field -1 0 4)"), "15");
+    assert_eq!(run_str("(bit-field 5 3 3)"), "0");
+    assert_eq!(run_str("(bit-field 18446744073709551615 60 68)"), "15");
+    assert_eq!(run_str("(bit-field 1 0 100000000000000)"), "1");
+    assert_eq!(
+        run_str("(bit-field -1 0 100000000000000)"),
+        "Error: bit field too large: 100000000000000"
+    );
+    assert_eq!(
+        run_str("(bit-field 5 -1 3)"),
+        "Error: expected non-negative bit index, got: -1"
+    );
+    assert_eq!(
+        run_str("(bit-field 5 4 3)"),
+        "Error: bit field start 4 is after its end 3"
+    );
+}
+
+#[test]
+fn non_integer_arguments() {
+    assert_eq!(
+        run_str("(bitwise-and 1.0 3)"),
+        "Error: expected exact integer, got: 1.0"
+    );
+    assert_eq!(
+        run_str("(bitwise-not 1/2)"),
+        "Error: expected exact integer, got: 1/2"
+    );
+    assert_eq!(
+        run_str("(arithmetic-shift 1 0.5)"),
+        "Error: expected exact integer, got: 0.5"
+    );
+    assert_eq!(run_str("(bit-count 'a)"), "Error: expected number, got: a");
+    assert_eq!(
+        run_str("(bitwise-not 1 2)"),
+        "Error: function takes 1 argument"
+    );
+}
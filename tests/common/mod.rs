@@ -0,0 +1,32 @@
+#![allow(dead_code)]
+
+use plib::env::Env;
+use plib::eval::eval;
+use plib::lexer::tokenize;
+use plib::parser::read_from_tokens;
+use plib::sexp::{Atom, Error, Sexp};
+
+/// Evaluates every expression in `code` in a fresh default environment and
+/// returns the value of the last one.
+pub fn run(code: &str) -> Result<Sexp, Error> {
+    run_in(code, &Env::default_env())
+}
+
+/// Same as `run`, but evaluates in the supplied environment.
+pub fn run_in(code: &str, env: &Env) -> Result<Sexp, Error> {
+    let mut tokens = tokenize(code.to_owned()).map_err(|err| Error::Reason(err.to_string()))?;
+    let mut result = Sexp::Atom(Atom::Nil);
+    while !tokens.is_empty() {
+        let exp = read_from_tokens(&mut tokens)?;
+        result = eval(&exp, env)?;
+    }
+    Ok(result)
+}
+
+/// Same as `run`, but returns printed representation of the result.
+pub fn run_str(code: &str) -> String {
+    match run(code) {
+        Ok(sexp) => sexp.to_string(),
+        Err(err) => err.to_string(),
+    }
+}
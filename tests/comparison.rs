@@ -0,0 +1,70 @@
+mod common;
+
+use common::{run, run_str};
+use plib::number::Number;
+
+#[test]
+fn number_equality() {
+    assert_eq!(Number::Int(1), Number::Int(1));
+    assert_eq!(Number::Int(1), Number::Float(1.0));
+    assert_ne!(Number::Int(1), Number::Float(1.5));
+    assert_ne!(Number::Float(f64::NAN), Number::Float(f64::NAN));
+    assert!(Number::Int(1) < Number::Float(1.5));
+}
+
+#[test]
+fn chained_comparisons() {
+    assert_eq!(run_str("(< 1 2 3)"), "true");
+    assert_eq!(run_str("(< 1 3 2)"), "false");
+    assert_eq!(run_str("(<= 1 1 2)"), "true");
+    assert_eq!(run_str("(> 3 2 1)"), "true");
+    assert_eq!(run_str("(>= 3 3 4)"), "false");
+    assert_eq!(run_str("(= 2 2 2)"), "true");
+    assert_eq!(run_str("(= 2 2 3)"), "false");
+    assert_eq!(run_str("(< 1)"), "true");
+    assert!(run("(<)").is_err());
+    assert_eq!(run_str("(=)"), "Error: function takes at least 1 argument");
+}
+
+#[test]
+fn mixed_comparisons() {
+    assert_eq!(run_str("(= 1 1.0)"), "true");
+    assert_eq!(run_str("(< 1 1.5 2)"), "true");
+    assert_eq!(run_str("(>= 2.0 2 1.9)"), "true");
+}
+
+#[test]
+fn nan_comparisons() {
+    let nan = "(define not-a-number (/ 0.0 0.0))";
+    for code in &[
+        "(= not-a-number not-a-number)",
+        "(< not-a-number 1)",
+        "(> not-a-number 1)",
+        "(<= not-a-number not-a-number)",
+        "(>= 1 not-a-number)",
+    ] {
+        assert_eq!(run_str(&format!("{} {}", nan, code)), "false");
+    }
+}
+
+#[test]
+fn comparison_checks_every_argument() {
+    assert_eq!(run_str("(< 2 1 'a)"), "Error: expected number, got: a");
+}
+
+#[test]
+fn numeric_predicates() {
+    assert_eq!(run_str("(zero? 0)"), "true");
+    assert_eq!(run_str("(zero? 0.0)"), "true");
+    assert_eq!(run_str("(zero? 1)"), "false");
+    assert_eq!(run_str("(positive? 2.5)"), "true");
+    assert_eq!(run_str("(positive? 0)"), "false");
+    assert_eq!(run_str("(negative? -1)"), "true");
+    assert_eq!(run_str("(negative? 0)"), "false");
+    assert_eq!(run_str("(even? 4)"), "true");
+    assert_eq!(run_str("(even? -3)"), "false");
+    assert_eq!(run_str("(odd? -3)"), "true");
+    assert_eq!(run_str("(odd? 4.0)"), "false");
+    assert_eq!(run_str("(even? 1.5)"), "Error: expected integer, got: 1.5");
+    assert_eq!(run_str("(zero? 'a)"), "Error: expected number, got: a");
+}
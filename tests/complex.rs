@@ -0,0 +1,110 @@
+mod common;
+
+use common::run_str;
+
+#[test]
+fn literals() {
+    assert_eq!(run_str("1+2i"), "1+2i");
+    assert_eq!(run_str("1.5-2.5i"), "1.5-2.5i");
+    assert_eq!(run_str("+i"), "+i");
+    assert_eq!(run_str("-i"), "-i");
+    assert_eq!(run_str("-2i"), "-2i");
+    assert_eq!(run_str("3-i"), "3-i");
+    assert_eq!(run_str("1e3+2e-1i"), "1000.0+0.2i");
+    assert_eq!(run_str("1/2+1/4i"), "1/2+1/4i");
+    assert_eq!(run_str("(int? 5+0i)"), "true");
+    assert_eq!(run_str("(symbol? 'pi)"), "true");
+    assert_eq!(run_str("(symbol? 'hi-i)"), "true");
+}
+
+#[test]
+fn sqrt_of_negative_numbers() {
+    assert_eq!(run_str("(sqrt -1)"), "+i");
+    assert_eq!(run_str("(sqrt -4)"), "+2i");
+    assert_eq!(run_str("(sqrt -1/4)"), "+1/2i");
+    assert_eq!(run_str("(eqv? (sqrt -1) +i)"), "true");
+    assert_eq!(run_str("(sqrt -1.0)"), "+1.0i");
+    assert_eq!(run_str("(sqrt -2)"), "+1.4142135623730951i");
+    assert_eq!(run_str("(sqrt 4)"), "2");
+    assert_eq!(run_str("(sqrt -2.0i)"), "1.0-1.0i");
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(run_str("(+ 1+2i 3-i)"), "4+i");
+    assert_eq!(run_str("(- 1+2i 1)"), "+2i");
+    assert_eq!(run_str("(* 1+2i 3-i)"), "5+5i");
+    assert_eq!(run_str("(* +i +i)"), "-1");
+    assert_eq!(run_str("(int? (* +i +i))"), "true");
+    assert_eq!(run_str("(* +1.0i +i)"), "-1.0");
+    assert_eq!(run_str("(float? (* +1.0i +i))"), "true");
+    assert_eq!(run_str("(/ 5+5i 3-i)"), "1+2i");
+    assert_eq!(run_str("(/ 5.0+5.0i 3-i)"), "1.0+2.0i");
+    assert_eq!(run_str("(expt +i 2)"), "-1");
+    assert_eq!(run_str("(expt 1+i -2)"), "-1/2i");
+    assert_eq!(run_str("(/ 1+i 0)"), "Error: division by zero");
+    assert_eq!(run_str("(+ 1/2 +i)"), "1/2+i");
+}
+
+#[test]
+fn constructors() {
+    assert_eq!(run_str("(make-rectangular 1 2)"), "1+2i");
+    assert_eq!(run_str("(make-rectangular 1 2.0)"), "1.0+2.0i");
+    assert_eq!(run_str("(make-rectangular 1/2 0)"), "1/2");
+    assert_eq!(run_str("(make-polar 2 0)"), "2");
+    assert_eq!(run_str("(magnitude (make-polar 2 1))"), "2.0");
+    assert_eq!(run_str("(angle (make-polar 2 1))"), "1.0");
+    assert_eq!(
+        run_str("(make-rectangular +i 1)"),
+        "Error: expected real number, got: +i"
+    );
+}
+
+#[test]
+fn accessors() {
+    assert_eq!(run_str("(real-part 3-4i)"), "3");
+    assert_eq!(run_str("(imag-part 3-4i)"), "-4");
+    assert_eq!(run_str("(imag-part 3.0-4.0i)"), "-4.0");
+    assert_eq!(run_str("(imag-part 3)"), "0");
+    assert_eq!(run_str("(magnitude 3-4i)"), "5");
+    assert_eq!(run_str("(magnitude 1+i)"), "1.4142135623730951");
+    assert_eq!(run_str("(magnitude 3.0-4.0i)"), "5.0");
+    assert_eq!(run_str("(magnitude -7/2)"), "7/2");
+    assert_eq!(run_str("(angle +i)"), "1.5707963267948966");
+    assert_eq!(run_str("(angle -1)"), "3.141592653589793");
+    assert_eq!(run_str("(angle 5)"), "0");
+}
+
+#[test]
+fn exp_and_log() {
+    assert_eq!(run_str("(exp 0)"), "1.0");
+    assert_eq!(run_str("(log 1)"), "0.0");
+    assert_eq!(run_str("(log -1)"), "+3.141592653589793i");
+    assert_eq!(run_str("(real-part (exp (log -1)))"), "-1.0");
+    assert_eq!(run_str("(magnitude (exp +3.141592653589793i))"), "1.0");
+}
+
+#[test]
+fn equality_and_ordering() {
+    assert_eq!(run_str("(= 1+2i 1+2i)"), "true");
+    assert_eq!(run_str("(= 1+2i 1)"), "false");
+    assert_eq!(run_str("(= 1+2i 1.0+2.0i)"), "true");
+    assert_eq!(run_str("(eqv? 1+2i 1+2i)"), "true");
+    assert_eq!(run_str("(eqv? 1+2i 1.0+2.0i)"), "false");
+    assert_eq!(
+        run_str("(< 1+2i 2)"),
+        "Error: expected real number, got: 1+2i"
+    );
+    assert_eq!(
+        run_str("(max 1 +i)"),
+        "Error: expected real number, got: +i"
+    );
+    assert_eq!(run_str("(abs -i)"), "Error: expected real number, got: -i");
+    assert_eq!(run_str("(zero? +i)"), "false");
+    assert_eq!(run_str("(inexact->exact 0.5+1.0i)"), "1/2+i");
+    assert_eq!(run_str("(exact->inexact 1/2+i)"), "0.5+1.0i");
+    assert_eq!(
+        run_str("(inexact->exact 1.0+inf.0i)"),
+        "Error: no exact representation of: 1.0+inf.0i"
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::run_in;
+use plib::env::Env;
+use plib::number::Number;
+use plib::sexp::{Atom, Sexp};
+
+fn int(i: i32) -> Sexp {
+    Sexp::Atom(Atom::Number(Number::Int(i)))
+}
+
+#[test]
+fn child_frame_shadows_outer() {
+    let outer = Env::new(None);
+    outer.insert("x".to_owned(), int(1));
+    let inner = Env::new(Some(outer.clone()));
+    inner.insert("x".to_owned(), int(2));
+
+    assert_eq!(inner.search("x").unwrap().to_string(), "2");
+    assert_eq!(outer.search("x").unwrap().to_string(), "1");
+}
+
+#[test]
+fn set_walks_the_chain() {
+    let outer = Env::new(None);
+    outer.insert("x".to_owned(), int(1));
+    let inner = Env::new(Some(outer.clone()));
+
+    inner.set("x", int(2)).unwrap();
+    assert_eq!(outer.search("x").unwrap().to_string(), "2");
+    assert!(inner.set("y", int(3)).is_err());
+}
+
+#[test]
+fn returned_closure_outlives_its_frame() {
+    let env = Env::default_env();
+    let make = "((lambda (x) (lambda () x)) 'kept)";
+    let closure = run_in(make, &env).unwrap();
+    env.insert("f".to_owned(), closure);
+
+    assert_eq!(run_in("(f)", &env).unwrap().to_string(), "kept");
+}
+
+#[test]
+fn closure_observes_later_mutation() {
+    let env = Env::default_env();
+    env.insert("x".to_owned(), int(1));
+    let closure = run_in("(lambda () x)", &env).unwrap();
+    env.insert("f".to_owned(), closure);
+    env.set("x", int(2)).unwrap();
+
+    assert_eq!(run_in("(f)", &env).unwrap().to_string(), "2");
+}
+
+#[test]
+fn lambda_refers_to_itself() {
+    let env = Env::default_env();
+    let closure = run_in("(lambda (x) (cons x f))", &env).unwrap();
+    env.insert("f".to_owned(), closure);
+
+    assert_eq!(
+        run_in("(f 1)", &env).unwrap().to_string(),
+        "(1 . lambda function)"
+    );
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::{run, run_str};
+use plib::number::Number;
+use plib::sexp::{Atom, Sexp};
+use std::rc::Rc;
+
+fn int(i: i32) -> Sexp {
+    Sexp::Atom(Atom::Number(Number::Int(i)))
+}
+
+/// Full binary tree of the given depth with numbered leaves.
+fn tree(depth: u32, leaf: &mut i32) -> Sexp {
+    if depth == 0 {
+        *leaf += 1;
+        return int(*leaf);
+    }
+    Sexp::Cons {
+        car: Rc::new(tree(depth - 1, leaf)),
+        cdr: Rc::new(tree(depth - 1, leaf)),
+    }
+}
+
+#[test]
+fn atom_equality() {
+    assert_eq!(Atom::Nil, Atom::Nil);
+    assert_eq!(Atom::Symbol("a".to_owned()), Atom::Symbol("a".to_owned()));
+    assert_ne!(Atom::Symbol("a".to_owned()), Atom::Char('a'));
+    assert_eq!(Atom::Number(Number::Int(1)), Atom::Number(Number::Int(1)));
+    assert_ne!(
+        Atom::Number(Number::Int(1)),
+        Atom::Number(Number::Float(1.0))
+    );
+    assert_eq!(
+        Atom::Number(Number::Float(f64::NAN)),
+        Atom::Number(Number::Float(f64::NAN))
+    );
+}
+
+#[test]
+fn deep_tree_equality() {
+    let a = tree(12, &mut 0);
+    let b = tree(12, &mut 0);
+    assert_eq!(a, b);
+
+    let c = tree(12, &mut 1);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn long_list_equality() {
+    let a = Sexp::list((0..10_000).map(int).collect());
+    let mut b = Sexp::list((0..10_000).map(int).collect());
+    assert_eq!(a, b);
+
+    if let Sexp::Cons { car, .. } = &mut b {
+        *car = Rc::new(int(-1));
+    }
+    assert_ne!(a, b);
+}
+
+#[test]
+fn builtins_compare_by_name() {
+    let env = plib::env::Env::default_env();
+    assert_eq!(env.search("cons").unwrap(), env.search("cons").unwrap());
+    assert_ne!(env.search("cons").unwrap(), env.search("+").unwrap());
+    assert_eq!(run_str("(eqv? cons cons)"), "true");
+    assert_eq!(run_str("(eqv? cons +)"), "false");
+}
+
+#[test]
+fn eqv_on_atoms() {
+    assert_eq!(run_str("(eqv? 'a 'a)"), "true");
+    assert_eq!(run_str("(eqv? 'a 'b)"), "false");
+    assert_eq!(run_str("(eqv? 2 2)"), "true");
+    assert_eq!(run_str("(eqv? 2 2.0)"), "false");
+    assert_eq!(run_str("(eqv? nil '())"), "true");
+    assert_eq!(run_str("(eq? true true)"), "true");
+    assert_eq!(run_str("(eq? 'a 'a)"), "true");
+}
+
+#[test]
+fn eqv_on_conses() {
+    assert_eq!(run_str("(eqv? '(1) '(1))"), "false");
+    assert_eq!(run_str("(eq? (cons 1 2) (cons 1 2))"), "false");
+    assert_eq!(run_str("(define x '(1 2)) (eq? x x)"), "true");
+    assert_eq!(
+        run_str("(define x (cons 1 2)) (eqv? x (car (list x)))"),
+        "true"
+    );
+}
+
+#[test]
+fn eqv_on_closures() {
+    assert_eq!(run_str("(define f (lambda (x) x)) (eqv? f f)"), "true");
+    assert_eq!(
+        run_str("(define (make) (lambda (x) x)) (eqv? (make) (make))"),
+        "false"
+    );
+}
+
+#[test]
+fn equal_on_trees() {
+    assert_eq!(
+        run_str("(equal? '(1 (2 (3 . 4)) (a b) nil) '(1 (2 (3 . 4)) (a b) nil))"),
+        "true"
+    );
+    assert_eq!(
+        run_str("(equal? '(1 (2 (3 . 4))) '(1 (2 (3 . 5))))"),
+        "false"
+    );
+    assert_eq!(run_str("(equal? '(1 2) '(1 2 3))"), "false");
+    assert_eq!(run_str("(equal? '(1 2) '(1.0 2))"), "false");
+    assert_eq!(run_str("(equal? 'a 'a)"), "true");
+}
+
+#[test]
+fn equality_arity() {
+    assert!(run("(equal? 1)").is_err());
+    assert!(run("(eq? 1 2 3)").is_err());
+    assert_eq!(run_str("(eqv? 1)"), "Error: function takes 2 arguments");
+}
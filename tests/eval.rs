@@ -0,0 +1,371 @@
+mod common;
+
+use common::{run, run_str};
+
+#[test]
+fn lambda_call() {
+    assert_eq!(run_str("((lambda (x y) (cons y x)) 1 2)"), "(2 . 1)");
+}
+
+#[test]
+fn lambda_without_params() {
+    assert_eq!(run_str("((lambda () 'a))"), "a");
+}
+
+#[test]
+fn lambda_evaluates_whole_body() {
+    assert_eq!(run_str("((lambda (x) 'ignored x) 3)"), "3");
+}
+
+#[test]
+fn lambda_rest_params() {
+    assert_eq!(run_str("((lambda args args) 1 2)"), "(1 . (2 . nil))");
+    assert_eq!(
+        run_str("((lambda (x . rest) rest) 1 2 3)"),
+        "(2 . (3 . nil))"
+    );
+}
+
+#[test]
+fn lambda_as_argument() {
+    assert_eq!(
+        run_str("((lambda (f) (f 3)) (lambda (x) (cons x x)))"),
+        "(3 . 3)"
+    );
+}
+
+#[test]
+fn closure_captures_defining_env() {
+    assert_eq!(
+        run_str("(((lambda (x) (lambda (y) (cons x y))) 1) 2)"),
+        "(1 . 2)"
+    );
+}
+
+#[test]
+fn lambda_is_function() {
+    assert_eq!(run_str("(function? (lambda (x) x))"), "true");
+}
+
+#[test]
+fn lambda_arity_errors() {
+    assert!(run("((lambda (x y) x) 1)").is_err());
+    assert!(run("((lambda (x) x) 1 2)").is_err());
+    assert!(run("(lambda (1) x)").is_err());
+    assert!(run("(lambda (x))").is_err());
+}
+
+#[test]
+fn define_binds_value() {
+    assert_eq!(run_str("(define x 'a) x"), "a");
+}
+
+#[test]
+fn define_function_shorthand() {
+    assert_eq!(
+        run_str("(define (pair a b) (cons a b)) (pair 1 2)"),
+        "(1 . 2)"
+    );
+}
+
+#[test]
+fn define_inside_lambda_is_local() {
+    assert_eq!(run_str("(define (f) (define y 1) y) (f)"), "1");
+    assert!(run("(define (f) (define y 1) y) (f) y").is_err());
+}
+
+#[test]
+fn set_rebinds_existing_binding() {
+    assert_eq!(run_str("(define x 1) (set! x 2) x"), "2");
+}
+
+#[test]
+fn set_updates_captured_binding() {
+    let code = "
+        (define (make-counter)
+          (define ticks nil)
+          (lambda () (set! ticks (cons 'tick ticks)) ticks))
+        (define counter (make-counter))
+        (counter)
+        (counter)";
+    assert_eq!(run_str(code), "(tick . (tick . nil))");
+}
+
+#[test]
+fn set_unbound_symbol_fails() {
+    assert_eq!(
+        run_str("(set! missing 1)"),
+        "Error: cannot set unbound symbol: missing"
+    );
+}
+
+#[test]
+fn malformed_define_fails() {
+    assert!(run("(define 1 2)").is_err());
+    assert!(run("(define x 1 2)").is_err());
+    assert!(run("(set! x)").is_err());
+}
+
+#[test]
+fn if_branches() {
+    assert_eq!(run_str("(if true 'yes 'no)"), "yes");
+    assert_eq!(run_str("(if nil 'yes 'no)"), "no");
+    assert_eq!(run_str("(if false 'yes)"), "nil");
+    assert_eq!(run_str("(if 0 'yes 'no)"), "yes");
+}
+
+#[test]
+fn if_evaluates_only_taken_branch() {
+    assert_eq!(run_str("(if true 'yes (undefined))"), "yes");
+    assert!(run("(if)").is_err());
+}
+
+#[test]
+fn cond_clauses() {
+    let code = "
+        (define (classify x)
+          (cond ((null? x) 'null)
+                ((cons? x) 'cons)
+                (else 'other)))
+        (cons (classify nil) (cons (classify '(1)) (classify 1)))";
+    assert_eq!(run_str(code), "(null . (cons . other))");
+}
+
+#[test]
+fn cond_test_only_and_arrow_clauses() {
+    assert_eq!(run_str("(cond (false 1) ('value))"), "value");
+    assert_eq!(run_str("(cond ('(1 2) => cons?) (else 'no))"), "true");
+    assert_eq!(run_str("(cond (false 1))"), "nil");
+}
+
+#[test]
+fn when_and_unless() {
+    assert_eq!(run_str("(when true 'a 'b)"), "b");
+    assert_eq!(run_str("(when false (undefined))"), "nil");
+    assert_eq!(run_str("(unless false 'a)"), "a");
+    assert_eq!(run_str("(unless true (undefined))"), "nil");
+}
+
+#[test]
+fn and_or_short_circuit() {
+    assert_eq!(run_str("(and)"), "true");
+    assert_eq!(run_str("(and 1 2 'last)"), "last");
+    assert_eq!(run_str("(and 1 false (undefined))"), "false");
+    assert_eq!(run_str("(or)"), "false");
+    assert_eq!(run_str("(or nil 'first (undefined))"), "first");
+    assert_eq!(run_str("(or false nil)"), "nil");
+}
+
+#[test]
+fn quote_returns_argument_unevaluated() {
+    assert_eq!(run_str("(quote (undefined 1))"), "(undefined . (1 . nil))");
+    assert_eq!(run_str("'symbol"), "symbol");
+    assert!(run("(quote)").is_err());
+}
+
+#[test]
+fn special_forms_cannot_be_shadowed() {
+    assert_eq!(
+        run_str("(define if 1)"),
+        "Error: cannot bind special form name: if"
+    );
+    assert!(run("(define (quote x) x)").is_err());
+    assert!(run("(lambda (and) and)").is_err());
+    assert!(run("(lambda lambda 1)").is_err());
+}
+
+#[test]
+fn special_form_is_not_a_value() {
+    assert!(run("(function? if)").is_err());
+}
+
+#[test]
+fn let_binds_in_child_frame() {
+    assert_eq!(run_str("(let ((x 'a) (y 'b)) (cons x y))"), "(a . b)");
+    assert_eq!(
+        run_str("(define x 'outer) (let ((x 'inner) (y x)) y)"),
+        "outer"
+    );
+    assert!(run("(let ((x 'a)) x) x").is_err());
+}
+
+#[test]
+fn let_star_binds_sequentially() {
+    assert_eq!(run_str("(let* ((x 'a) (y (cons x x))) y)"), "(a . a)");
+    assert_eq!(run_str("(let* ((x 'a) (x (cons x x))) x)"), "(a . a)");
+    assert_eq!(run_str("(let* () 'empty)"), "empty");
+}
+
+#[test]
+fn letrec_allows_mutual_recursion() {
+    let code = "
+        (letrec ((f (lambda () (g)))
+                 (g (lambda () 'done)))
+          (f))";
+    assert_eq!(run_str(code), "done");
+}
+
+#[test]
+fn letrec_star_sees_earlier_values() {
+    assert_eq!(run_str("(letrec* ((a 'x) (b (cons a a))) b)"), "(x . x)");
+}
+
+#[test]
+fn named_let_loops() {
+    let code = "
+        (let loop ((x 'start) (acc nil))
+          (if (null? acc)
+              (loop x (cons x acc))
+              acc))";
+    assert_eq!(run_str(code), "(start . nil)");
+}
+
+#[test]
+fn malformed_let_bindings_fail() {
+    assert_eq!(
+        run_str("(let ((x)) x)"),
+        "Error: malformed let binding: (x . nil)"
+    );
+    assert!(run("(let ((1 2)) 1)").is_err());
+    assert!(run("(let x)").is_err());
+    assert!(run("(let ((x 1)))").is_err());
+    assert!(run("(let* (x) x)").is_err());
+    assert!(run("(letrec ((if 1)) 1)").is_err());
+}
+
+#[test]
+fn duplicate_let_bindings_fail() {
+    assert_eq!(
+        run_str("(let ((x 1) (x 2)) x)"),
+        "Error: duplicate let binding: x"
+    );
+    assert!(run("(letrec ((x 1) (x 2)) x)").is_err());
+    assert!(run("(let loop ((x 1) (x 2)) x)").is_err());
+}
+
+#[test]
+fn duplicate_lambda_parameters_fail() {
+    assert_eq!(
+        run_str("(lambda (x x) x)"),
+        "Error: duplicate lambda parameter: x"
+    );
+    assert!(run("(lambda (x y . x) x)").is_err());
+    assert!(run("(define (f a b a) a)").is_err());
+    assert_eq!(run_str("((lambda (x . y) y) 1 2)"), "(2 . nil)");
+}
+
+#[test]
+fn quasiquote_without_unquote_is_quote() {
+    assert_eq!(run_str("`(a b)"), run_str("'(a b)"));
+    assert_eq!(run_str("`a"), "a");
+}
+
+#[test]
+fn quasiquote_unquote() {
+    assert_eq!(run_str("(define x 'y) `(a ,x)"), "(a . (y . nil))");
+    assert_eq!(run_str("`(1 ,(cons 1 2))"), "(1 . ((1 . 2) . nil))");
+    assert_eq!(run_str("(define x 'y) `(a . ,x)"), "(a . y)");
+}
+
+#[test]
+fn quasiquote_unquote_splicing() {
+    assert_eq!(
+        run_str("(define xs '(2 3)) `(1 ,@xs 4)"),
+        "(1 . (2 . (3 . (4 . nil))))"
+    );
+    assert_eq!(run_str("`(1 ,@nil)"), "(1 . nil)");
+    assert_eq!(run_str("`(,@'(1 2))"), "(1 . (2 . nil))");
+    assert!(run("`(1 ,@'a)").is_err());
+}
+
+#[test]
+fn nested_quasiquote_keeps_inner_unquotes() {
+    assert_eq!(
+        run_str("(define x 'y) `(a `(b ,(c ,x)))"),
+        run_str("'(a (quasiquote (b (unquote (c y)))))")
+    );
+    assert_eq!(
+        run_str("`(a `(b ,@(c ,@'(1 2))))"),
+        run_str("'(a (quasiquote (b (unquote-splicing (c 1 2)))))")
+    );
+}
+
+#[test]
+fn unquote_outside_quasiquote_fails() {
+    assert!(run(",a").is_err());
+    assert!(run("(cons ,@a nil)").is_err());
+}
+
+#[test]
+fn defmacro_receives_unevaluated_arguments() {
+    let code = "
+        (defmacro my-if (test then else)
+          `(cond (,test ,then) (else ,else)))
+        (my-if true 'yes (undefined))";
+    assert_eq!(run_str(code), "yes");
+}
+
+#[test]
+fn defmacro_expansion_is_evaluated_in_caller_env() {
+    let code = "
+        (defmacro swap! (a b)
+          `(let ((tmp ,a)) (set! ,a ,b) (set! ,b tmp)))
+        (define x 1)
+        (define y 2)
+        (swap! x y)
+        (cons x y)";
+    assert_eq!(run_str(code), "(2 . 1)");
+}
+
+#[test]
+fn defmacro_rest_params() {
+    let code = "
+        (defmacro my-when (test . body) `(if ,test (let () ,@body)))
+        (my-when true 'a 'b)";
+    assert_eq!(run_str(code), "b");
+}
+
+#[test]
+fn macros_expand_before_evaluation() {
+    let code = "
+        (define expansions 0)
+        (defmacro counted (x)
+          (set! expansions (+ expansions 1))
+          x)
+        (let loop ((i 0))
+          (if (= i 5)
+              expansions
+              (loop (+ (counted i) 1))))";
+    assert_eq!(run_str(code), "1");
+    let code = "
+        (defmacro m (x) ''macro)
+        (cons ((lambda (m) (m 1)) (lambda (x) x))
+              (let ((m (lambda (x) x))) (m 2)))";
+    assert_eq!(run_str(code), "(1 . 2)");
+}
+
+#[test]
+fn macroexpand_builtins() {
+    let defs = "
+        (defmacro m1 (x) `(m2 ,x))
+        (defmacro m2 (x) `(quote ,x))";
+    assert_eq!(
+        run_str(&format!("{} (macroexpand-1 '(m1 a))", defs)),
+        "(m2 . (a . nil))"
+    );
+    assert_eq!(
+        run_str(&format!("{} (macroexpand '(m1 a))", defs)),
+        "(quote . (a . nil))"
+    );
+    assert_eq!(
+        run_str(&format!("{} (macroexpand '(cons 1 2))", defs)),
+        "(cons . (1 . (2 . nil)))"
+    );
+}
+
+#[test]
+fn malformed_defmacro_fails() {
+    assert!(run("(defmacro m)").is_err());
+    assert!(run("(defmacro (m x) x)").is_err());
+    assert!(run("(defmacro if (x) x)").is_err());
+}
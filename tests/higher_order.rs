@@ -0,0 +1,125 @@
+mod common;
+
+use common::{run, run_str};
+
+#[test]
+fn map_over_one_and_many_lists() {
+    assert_eq!(
+        run_str("(map (lambda (x) (* x x)) '(1 2 3))"),
+        "(1 . (4 . (9 . nil)))"
+    );
+    assert_eq!(
+        run_str("(map + '(1 2 3) '(10 20 30))"),
+        "(11 . (22 . (33 . nil)))"
+    );
+    assert_eq!(run_str("(map + '(1 2 3) '(10 20))"), "(11 . (22 . nil))");
+    assert_eq!(run_str("(map car '((a 1) (b 2)))"), "(a . (b . nil))");
+    assert_eq!(run_str("(map (lambda (x) x) '())"), "nil");
+    assert_eq!(run_str("(map 1 '())"), "Error: expected function, got: 1");
+    assert_eq!(
+        run_str("(map + '(1 . 2))"),
+        "Error: expected list, got: (1 . 2)"
+    );
+}
+
+#[test]
+fn builtins_taking_the_environment_can_be_passed() {
+    assert_eq!(
+        run_str("(map map (list car cdr) '(((1 2)) ((3 4))))"),
+        "((1 . nil) . (((4 . nil) . nil) . nil))"
+    );
+    assert_eq!(
+        run_str("(apply map list '((1 2) (3 4)))"),
+        "((1 . (3 . nil)) . ((2 . (4 . nil)) . nil))"
+    );
+}
+
+#[test]
+fn for_each_runs_for_side_effects() {
+    let code = "(define total 0) \
+                (for-each (lambda (x y) (set! total (+ total (* x y)))) '(1 2 3) '(4 5 6)) \
+                total";
+    assert_eq!(run_str(code), "32");
+    assert_eq!(run_str("(for-each car '((1)))"), "nil");
+}
+
+#[test]
+fn filter_and_find() {
+    assert_eq!(
+        run_str("(filter odd? '(1 2 3 4 5))"),
+        "(1 . (3 . (5 . nil)))"
+    );
+    assert_eq!(run_str("(filter odd? '())"), "nil");
+    assert_eq!(run_str("(find even? '(1 3 4 6))"), "4");
+    assert_eq!(run_str("(find even? '(1 3))"), "false");
+}
+
+#[test]
+fn folds() {
+    assert_eq!(run_str("(reduce + 0 '(1 2 3 4))"), "10");
+    assert_eq!(run_str("(reduce + 0 '())"), "0");
+    assert_eq!(run_str("(reduce cons nil '(1 2 3))"), "(3 . (2 . 1))");
+    assert_eq!(
+        run_str("(fold-left cons nil '(1 2 3))"),
+        "(((nil . 1) . 2) . 3)"
+    );
+    assert_eq!(
+        run_str("(fold-right cons nil '(1 2 3))"),
+        "(1 . (2 . (3 . nil)))"
+    );
+    assert_eq!(run_str("(fold-left - 0 '(1 2 3))"), "-6");
+    assert_eq!(run_str("(fold-right - 0 '(1 2 3))"), "2");
+    assert_eq!(run_str("(fold-left + 0 '(1 2) '(10 20))"), "33");
+    assert_eq!(
+        run_str("(fold-right list 'end '(1 2) '(a b))"),
+        "(1 . (a . ((2 . (b . (end . nil))) . nil)))"
+    );
+}
+
+#[test]
+fn any_and_every() {
+    assert_eq!(run_str("(any odd? '(2 4 5))"), "true");
+    assert_eq!(run_str("(any odd? '(2 4))"), "false");
+    assert_eq!(
+        run_str("(any (lambda (x) (and (odd? x) (* x 10))) '(2 3 5))"),
+        "30"
+    );
+    assert_eq!(run_str("(any < '(3 1) '(2 2))"), "true");
+    assert_eq!(run_str("(every odd? '(1 3))"), "true");
+    assert_eq!(run_str("(every odd? '(1 2 3))"), "false");
+    assert_eq!(run_str("(every odd? '())"), "true");
+    assert_eq!(run_str("(every (lambda (x) (* x 2)) '(1 2 3))"), "6");
+}
+
+#[test]
+fn apply_spreads_the_last_argument() {
+    assert_eq!(run_str("(apply + '(1 2 3))"), "6");
+    assert_eq!(run_str("(apply + 1 2 '(3 4))"), "10");
+    assert_eq!(run_str("(apply (lambda args args) 1 '())"), "(1 . nil)");
+    assert_eq!(run_str("(apply + 1 2)"), "Error: expected list, got: 2");
+    assert!(run("(apply +)").is_err());
+}
+
+#[test]
+fn sort_is_stable() {
+    assert_eq!(run_str("(sort '(3 1 2) <)"), "(1 . (2 . (3 . nil)))");
+    assert_eq!(run_str("(sort '() <)"), "nil");
+    assert_eq!(
+        run_str("(sort '((b . 1) (a . 2) (c . 1) (d . 2)) (lambda (x y) (< (cdr x) (cdr y))))"),
+        "((b . 1) . ((c . 1) . ((a . 2) . ((d . 2) . nil))))"
+    );
+    assert_eq!(
+        run_str("(sort '(5 3 9 1 7 3 0 2 8 6 4) >)"),
+        "(9 . (8 . (7 . (6 . (5 . (4 . (3 . (3 . (2 . (1 . (0 . nil)))))))))))"
+    );
+    assert_eq!(run_str("(sort '(1 a) <)"), "Error: expected number, got: a");
+}
+
+#[test]
+fn errors_in_callbacks_propagate() {
+    assert_eq!(run_str("(map car '(1))"), "Error: expected cons, got: 1");
+    assert_eq!(
+        run_str("(map (lambda (x y) x) '(1))"),
+        "Error: too few arguments passed to lambda"
+    );
+}
@@ -2,6 +2,7 @@ use plib::lexer;
 use std::collections::VecDeque;
 
 #[test]
+#[allow(clippy::useless_vec)]
 fn tokenize_parens() {
     let vec = vec![lexer::Token::LParen, lexer::Token::RParen];
     let tokens = lexer::tokenize("()".to_owned()).unwrap();
@@ -22,6 +23,7 @@ fn tokenize_symbol() {
 }
 
 #[test]
+#[allow(clippy::redundant_pattern_matching)]
 fn tokenize_symbol_error() {
     let is_err = match lexer::tokenize("dd'dd".to_owned()) {
         Ok(_) => false,
@@ -39,6 +41,7 @@ fn tokenize_string() {
 }
 
 #[test]
+#[allow(clippy::redundant_pattern_matching)]
 fn tokenize_string_error() {
     let is_err = match lexer::tokenize("\"ddd".to_owned()) {
         Ok(_) => false,
@@ -57,3 +60,100 @@ fn tokenize() {
     let result = lexer::tokenize("(string? \"string\")".to_owned()).unwrap();
     assert_eq!(expected, result)
 }
+
+#[test]
+fn tokenize_quasiquote() {
+    let mut expected = VecDeque::new();
+    expected.push_back(lexer::Token::Quasiquote);
+    expected.push_back(lexer::Token::LParen);
+    expected.push_back(lexer::Token::Unquote);
+    expected.push_back(lexer::Token::Symbol("a".to_owned()));
+    expected.push_back(lexer::Token::UnquoteSplicing);
+    expected.push_back(lexer::Token::Symbol("b".to_owned()));
+    expected.push_back(lexer::Token::RParen);
+    let result = lexer::tokenize("`(,a ,@b)".to_owned()).unwrap();
+    assert_eq!(expected, result)
+}
+
+#[test]
+fn tokenize_unquote_in_symbol_error() {
+    assert!(lexer::tokenize("a,b".to_owned()).is_err());
+    assert!(lexer::tokenize("a`b".to_owned()).is_err());
+}
+
+fn string_token(code: &str) -> lexer::Token {
+    let mut tokens = lexer::tokenize(code.to_owned()).unwrap();
+    assert_eq!(tokens.len(), 1);
+    tokens.pop_front().unwrap()
+}
+
+fn escape_error(code: &str) -> String {
+    lexer::tokenize(code.to_owned()).unwrap_err().to_string()
+}
+
+#[test]
+fn tokenize_string_escapes() {
+    let result = string_token(r#""a\"b\\c\nd\te\rf\0g""#);
+    let expected = lexer::Token::String("a\"b\\c\nd\te\rf\0g".to_owned());
+    assert_eq!(expected, result)
+}
+
+#[test]
+fn tokenize_string_code_point_escapes() {
+    assert_eq!(
+        lexer::Token::String("AλB😀".to_owned()),
+        string_token(r#""\x41;\x3bb;\u{42}\u{1F600}""#)
+    );
+    assert_eq!(
+        lexer::Token::String("\u{7}".to_owned()),
+        string_token(r#""\u{7}""#)
+    );
+}
+
+#[test]
+fn tokenize_string_line_continuation() {
+    assert_eq!(
+        lexer::Token::String("abcdef".to_owned()),
+        string_token("\"abc\\\n    def\"")
+    );
+    assert_eq!(
+        lexer::Token::String("abcdef".to_owned()),
+        string_token("\"abc\\  \r\n\tdef\"")
+    );
+    assert_eq!(
+        lexer::Token::String("abc\n  def".to_owned()),
+        string_token("\"abc\n  def\"")
+    );
+}
+
+#[test]
+fn tokenize_string_invalid_escapes() {
+    assert_eq!(
+        escape_error(r#""ab\qc""#),
+        r#"Lexer error: invalid escape \q after "ab" in string"#
+    );
+    assert_eq!(
+        escape_error(r#""\x41""#),
+        r#"Lexer error: invalid escape \x41 after "" in string"#
+    );
+    assert_eq!(
+        escape_error(r#""\xD800;""#),
+        r#"Lexer error: invalid escape \xD800; after "" in string"#
+    );
+    assert_eq!(
+        escape_error(r#""a\u{110000}""#),
+        r#"Lexer error: invalid escape \u{110000} after "a" in string"#
+    );
+    assert_eq!(
+        escape_error(r#""\u41""#),
+        r#"Lexer error: invalid escape \u after "" in string"#
+    );
+    assert_eq!(
+        escape_error("\"a\\ b\""),
+        r#"Lexer error: invalid escape \ followed by whitespace after "a" in string"#
+    );
+    assert_eq!(
+        escape_error("\"a\\"),
+        r#"Lexer error: invalid escape \ at end of input after "a" in string"#
+    );
+}
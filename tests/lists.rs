@@ -0,0 +1,122 @@
+mod common;
+
+use common::{run, run_str};
+
+#[test]
+fn car_cdr_and_compositions() {
+    assert_eq!(run_str("(car '(1 2 3))"), "1");
+    assert_eq!(run_str("(cdr '(1 2 3))"), "(2 . (3 . nil))");
+    assert_eq!(run_str("(cdr '(1 . 2))"), "2");
+    assert_eq!(run_str("(cadr '(1 2 3))"), "2");
+    assert_eq!(run_str("(cddr '(1 2 3))"), "(3 . nil)");
+    assert_eq!(run_str("(caddr '(1 2 3))"), "3");
+    assert_eq!(run_str("(caar '((1) 2))"), "1");
+    assert_eq!(run_str("(cdar '((1 . 4) 2))"), "4");
+    assert_eq!(run_str("(cadar '((1 5) 2))"), "5");
+    assert_eq!(run_str("(car '())"), "Error: expected cons, got: nil");
+    assert_eq!(run_str("(caddr '(1 2))"), "Error: expected cons, got: nil");
+    assert_eq!(run_str("(car 1 2)"), "Error: function takes 1 argument");
+}
+
+#[test]
+fn constructing_lists() {
+    assert_eq!(run_str("(list)"), "nil");
+    assert_eq!(run_str("(list 1 (+ 1 1) 'a)"), "(1 . (2 . (a . nil)))");
+    assert_eq!(run_str("(append)"), "nil");
+    assert_eq!(
+        run_str("(append '(1 2) '(3) '() '(4))"),
+        "(1 . (2 . (3 . (4 . nil))))"
+    );
+    assert_eq!(run_str("(append '(1) 2)"), "(1 . 2)");
+    assert_eq!(run_str("(append '() 'a)"), "a");
+    assert_eq!(
+        run_str("(append '(1 . 2) '(3))"),
+        "Error: expected list, got: (1 . 2)"
+    );
+    assert_eq!(run_str("(reverse '(1 2 3))"), "(3 . (2 . (1 . nil)))");
+    assert_eq!(run_str("(reverse '())"), "nil");
+}
+
+#[test]
+fn proper_lists_and_length() {
+    assert_eq!(run_str("(list? '(1 2))"), "true");
+    assert_eq!(run_str("(list? '())"), "true");
+    assert_eq!(run_str("(list? '(1 . 2))"), "false");
+    assert_eq!(run_str("(list? 1)"), "false");
+    assert_eq!(run_str("(length '(1 2 3))"), "3");
+    assert_eq!(run_str("(length '())"), "0");
+    assert_eq!(
+        run_str("(length '(1 2 . 3))"),
+        "Error: expected list, got: (1 . (2 . 3))"
+    );
+}
+
+#[test]
+fn long_lists() {
+    let code = "(define (build n acc) (if (zero? n) acc (build (- n 1) (cons n acc)))) \
+                (define l (build 3000 '())) \
+                (list (length l) (list? l) (car (reverse l)) (list-ref l 2999))";
+    assert_eq!(run_str(code), "(3000 . (true . (3000 . (3000 . nil))))");
+}
+
+#[test]
+fn indexing() {
+    assert_eq!(run_str("(list-ref '(a b c) 0)"), "a");
+    assert_eq!(run_str("(list-ref '(a b c) 2)"), "c");
+    assert_eq!(
+        run_str("(list-ref '(a b c) 3)"),
+        "Error: index 3 out of range for: (a . (b . (c . nil)))"
+    );
+    assert_eq!(
+        run_str("(list-ref '(a) -1)"),
+        "Error: expected non-negative index, got: -1"
+    );
+    assert_eq!(run_str("(list-tail '(a b c) 1)"), "(b . (c . nil))");
+    assert_eq!(run_str("(list-tail '(a b c) 3)"), "nil");
+    assert_eq!(run_str("(list-tail '(a . b) 1)"), "b");
+    assert!(run("(list-tail '(a b) 3)").is_err());
+    assert_eq!(run_str("(last-pair '(1 2 3))"), "(3 . nil)");
+    assert_eq!(run_str("(last-pair '(1 2 . 3))"), "(2 . 3)");
+    assert_eq!(run_str("(last-pair '())"), "Error: expected cons, got: nil");
+}
+
+#[test]
+fn membership() {
+    assert_eq!(run_str("(member 2 '(1 2 3))"), "(2 . (3 . nil))");
+    assert_eq!(run_str("(member 4 '(1 2 3))"), "false");
+    assert_eq!(
+        run_str("(member '(1) '((0) (1) (2)))"),
+        "((1 . nil) . ((2 . nil) . nil))"
+    );
+    assert_eq!(run_str("(memq '(1) '((0) (1) (2)))"), "false");
+    assert_eq!(run_str("(memq 'c '(a b c))"), "(c . nil)");
+    assert_eq!(
+        run_str("(define x '(1)) (memq x (list '(1) x))"),
+        "((1 . nil) . nil)"
+    );
+    assert_eq!(run_str("(member 2.0 '(1 2 3))"), "false");
+    assert_eq!(
+        run_str("(member 4 '(1 . 2))"),
+        "Error: expected list, got: (1 . 2)"
+    );
+}
+
+#[test]
+fn association_lists() {
+    let alist = "(define e '((a . 1) (b . 2) ((c) . 3)))";
+    assert_eq!(run_str(&format!("{} (assq 'b e)", alist)), "(b . 2)");
+    assert_eq!(run_str(&format!("{} (assq 'd e)", alist)), "false");
+    assert_eq!(
+        run_str(&format!("{} (assoc '(c) e)", alist)),
+        "((c . nil) . 3)"
+    );
+    assert_eq!(run_str(&format!("{} (assq '(c) e)", alist)), "false");
+    assert_eq!(
+        run_str("(define k '(c)) (assq k (list (cons '(c) 1) (cons k 2)))"),
+        "((c . nil) . 2)"
+    );
+    assert_eq!(
+        run_str("(assq 'a '(1 2))"),
+        "Error: expected association list, got: (1 . (2 . nil))"
+    );
+}
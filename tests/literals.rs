@@ -0,0 +1,132 @@
+mod common;
+
+use common::{run, run_str};
+
+#[test]
+fn radix_prefixes() {
+    assert_eq!(run_str("#x1F"), "31");
+    assert_eq!(run_str("#xff"), "255");
+    assert_eq!(run_str("#b1010"), "10");
+    assert_eq!(run_str("#o17"), "15");
+    assert_eq!(run_str("#d99"), "99");
+    assert_eq!(run_str("#x-10"), "-16");
+    assert_eq!(run_str("#x1/A"), "1/10");
+    assert_eq!(run_str("#xFFFFFFFFFFFFFFFF"), "18446744073709551615");
+}
+
+#[test]
+fn exactness_prefixes() {
+    assert_eq!(run_str("#e1.5"), "3/2");
+    assert_eq!(run_str("#e0.1"), "1/10");
+    assert_eq!(run_str("#e1e3"), "1000");
+    assert_eq!(run_str("#e1.5e-3"), "3/2000");
+    assert_eq!(run_str("(float? #i3)"), "true");
+    assert_eq!(run_str("#i1/4"), "0.25");
+    assert_eq!(run_str("#x#e10"), "16");
+    assert_eq!(run_str("#e#x10"), "16");
+    assert_eq!(run_str("#i#b101"), "5.0");
+}
+
+#[test]
+fn decimals_and_exponents() {
+    assert_eq!(run_str("1.5"), "1.5");
+    assert_eq!(run_str(".5"), "0.5");
+    assert_eq!(run_str("-.5"), "-0.5");
+    assert_eq!(run_str("1e3"), "1000.0");
+    assert_eq!(run_str("(float? 1e3)"), "true");
+    assert_eq!(run_str("2.5E-1"), "0.25");
+    assert_eq!(run_str("-1.5e+2"), "-150.0");
+    assert_eq!(run_str("2.0"), "2.0");
+    assert_eq!(run_str("-0.0"), "-0.0");
+    assert_eq!(run_str("1e21"), "1e21");
+    assert_eq!(run_str("1e-7"), "1e-7");
+}
+
+#[test]
+fn special_floats() {
+    assert_eq!(run_str("+inf.0"), "+inf.0");
+    assert_eq!(run_str("-inf.0"), "-inf.0");
+    assert_eq!(run_str("+nan.0"), "+nan.0");
+    assert_eq!(run_str("(< -inf.0 0 +inf.0)"), "true");
+    assert_eq!(run_str("(= +nan.0 +nan.0)"), "false");
+    assert_eq!(run_str("(/ -1 0.0)"), "-inf.0");
+    assert_eq!(
+        run_str("#e+inf.0"),
+        "Error: no exact representation of: +inf.0"
+    );
+    assert_eq!(run_str("(symbol? 'inf)"), "true");
+    assert_eq!(run_str("(symbol? 'nan)"), "true");
+}
+
+#[test]
+fn digit_separators() {
+    assert_eq!(run_str("1_000_000"), "1000000");
+    assert_eq!(run_str("#xFF_FF"), "65535");
+    assert_eq!(run_str("1_000.000_5"), "1000.0005");
+    assert_eq!(run_str("1_0/2_0"), "1/2");
+}
+
+#[test]
+fn complex_literals() {
+    assert_eq!(run_str("#x10+Ai"), "16+10i");
+    assert_eq!(run_str("+inf.0i"), "+inf.0i");
+    assert_eq!(run_str("1-inf.0i"), "1.0-inf.0i");
+    assert_eq!(run_str("1@0"), "1");
+}
+
+#[test]
+fn malformed_numerals() {
+    assert_eq!(
+        run_str("#xFG"),
+        "Error: invalid digit 'G' for radix 16 in numeral: #xFG"
+    );
+    assert_eq!(
+        run_str("#b102"),
+        "Error: invalid digit '2' for radix 2 in numeral: #b102"
+    );
+    assert_eq!(
+        run_str("12abc"),
+        "Error: invalid digit 'a' for radix 10 in numeral: 12abc"
+    );
+    assert_eq!(
+        run_str("1__0"),
+        "Error: misplaced digit separator in numeral: 1__0"
+    );
+    assert_eq!(
+        run_str("1_"),
+        "Error: misplaced digit separator in numeral: 1_"
+    );
+    assert_eq!(
+        run_str("1_.5"),
+        "Error: misplaced digit separator in numeral: 1_.5"
+    );
+    assert_eq!(run_str("1/0"), "Error: division by zero in numeral: 1/0");
+    assert_eq!(
+        run_str("#x1.5"),
+        "Error: decimal point in radix 16 in numeral: #x1.5"
+    );
+    assert_eq!(
+        run_str("#x#x1"),
+        "Error: duplicate radix prefix in numeral: #x#x1"
+    );
+    assert_eq!(
+        run_str("#e#i1"),
+        "Error: duplicate exactness prefix in numeral: #e#i1"
+    );
+    assert_eq!(run_str("1e"), "Error: missing digits in numeral: 1e");
+    assert_eq!(
+        run_str("2i"),
+        "Error: missing sign of imaginary part in numeral: 2i"
+    );
+    assert!(run("(+ 1 2x)").is_err());
+}
+
+#[test]
+fn symbols_are_not_numerals() {
+    assert_eq!(run_str("(symbol? '+)"), "true");
+    assert_eq!(run_str("(symbol? '-)"), "true");
+    assert_eq!(run_str("(symbol? '...)"), "true");
+    assert_eq!(run_str("(symbol? '->x)"), "true");
+    assert_eq!(run_str("(symbol? '#foo)"), "true");
+    assert_eq!(run_str("(symbol? 'x1)"), "true");
+}
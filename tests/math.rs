@@ -0,0 +1,181 @@
+mod common;
+
+use common::{run, run_str};
+use std::time::{Duration, Instant};
+
+#[test]
+fn exact_square_roots() {
+    assert_eq!(run_str("(sqrt 16)"), "4");
+    assert_eq!(run_str("(int? (sqrt 16))"), "true");
+    assert_eq!(run_str("(sqrt 9/4)"), "3/2");
+    assert_eq!(run_str("(sqrt 100000000000000000000)"), "10000000000");
+    assert_eq!(run_str("(sqrt 2)"), "1.4142135623730951");
+    assert_eq!(run_str("(sqrt -9)"), "+3i");
+    assert_eq!(run_str("(exact-integer-sqrt 17)"), "(4 . (1 . nil))");
+    assert_eq!(
+        run_str("(exact-integer-sqrt 4.0)"),
+        "Error: expected exact non-negative integer, got: 4.0"
+    );
+    assert!(run("(exact-integer-sqrt -1)").is_err());
+}
+
+#[test]
+fn powers() {
+    assert_eq!(run_str("(expt 2 10)"), "1024");
+    assert_eq!(run_str("(expt 2 100)"), "1267650600228229401496703205376");
+    assert_eq!(run_str("(expt 2/3 3)"), "8/27");
+    assert_eq!(run_str("(expt 2 -2)"), "1/4");
+    assert_eq!(run_str("(expt 0 0)"), "1");
+    assert_eq!(run_str("(expt 0 -1)"), "Error: division by zero");
+    assert_eq!(run_str("(expt 4 0.5)"), "2.0");
+    assert_eq!(run_str("(expt 2.0 3)"), "8.0");
+    assert_eq!(run_str("(expt -8 2)"), "64");
+    assert_eq!(
+        run_str("(expt 2 1000000000000)"),
+        "Error: exponent too large: 1000000000000"
+    );
+    assert_eq!(run_str("(expt 1 100000000000000000000)"), "1");
+    assert_eq!(run_str("(expt -1 100000000000000000001)"), "-1");
+    assert_eq!(run_str("(expt 0 100000000000000000000)"), "0");
+    assert_eq!(
+        run_str("(expt 0 -100000000000000000000)"),
+        "Error: division by zero"
+    );
+    assert_eq!(run_str("(= (expt -1 0.5) +i)"), "false");
+    assert_eq!(
+        run_str("(< (magnitude (- (expt -1 0.5) +i)) 1e-15)"),
+        "true"
+    );
+}
+
+#[test]
+fn large_powers_are_refused_before_computing_them() {
+    assert_eq!(run_str("(int? (expt 3 32768))"), "true");
+    let start = Instant::now();
+    assert_eq!(
+        run_str("(expt 3 1000000)"),
+        "Error: exponent too large: 1000000"
+    );
+    assert_eq!(
+        run_str("(expt 1+i 1000000)"),
+        "Error: exponent too large: 1000000"
+    );
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn exponentials_and_logarithms() {
+    assert_eq!(run_str("(exp 0)"), "1.0");
+    assert_eq!(run_str("(log 1)"), "0.0");
+    assert_eq!(run_str("(log 8 2)"), "3.0");
+    assert_eq!(run_str("(log 100 10)"), "2.0");
+    assert_eq!(run_str("(log 0)"), "-inf.0");
+    assert_eq!(run_str("(real-part (log -1))"), "0.0");
+    assert!(run("(log)").is_err());
+    assert!(run("(log 1 2 3)").is_err());
+}
+
+#[test]
+fn trigonometry() {
+    assert_eq!(run_str("(sin 0)"), "0.0");
+    assert_eq!(run_str("(cos 0)"), "1.0");
+    assert_eq!(run_str("(tan 0)"), "0.0");
+    assert_eq!(run_str("(asin 1)"), "1.5707963267948966");
+    assert_eq!(run_str("(acos 1)"), "0.0");
+    assert_eq!(run_str("(atan 1 1)"), "0.7853981633974483");
+    assert_eq!(run_str("(atan 1 -1)"), "2.356194490192345");
+    assert_eq!(run_str("(zero? (imag-part (asin 2)))"), "false");
+    assert_eq!(
+        run_str("(< (magnitude (- (sin (asin 2)) 2)) 1e-15)"),
+        "true"
+    );
+    assert_eq!(
+        run_str("(< (magnitude (- (tan (atan 1+i)) 1+i)) 1e-15)"),
+        "true"
+    );
+    assert_eq!(
+        run_str("(atan +i 1)"),
+        "Error: expected real number, got: +i"
+    );
+}
+
+#[test]
+fn rounding() {
+    assert_eq!(run_str("(floor -4.3)"), "-5.0");
+    assert_eq!(run_str("(ceiling -4.3)"), "-4.0");
+    assert_eq!(run_str("(truncate -4.3)"), "-4.0");
+    assert_eq!(run_str("(round -4.3)"), "-4.0");
+    assert_eq!(run_str("(floor 7/2)"), "3");
+    assert_eq!(run_str("(ceiling 7/2)"), "4");
+    assert_eq!(run_str("(truncate -7/2)"), "-3");
+    assert_eq!(run_str("(floor -7/2)"), "-4");
+    assert_eq!(run_str("(floor 2.5)"), "2.0");
+    assert_eq!(run_str("(int? (floor 5/2))"), "true");
+    assert_eq!(run_str("(floor 7)"), "7");
+}
+
+#[test]
+fn round_half_to_even() {
+    assert_eq!(run_str("(round 2.5)"), "2.0");
+    assert_eq!(run_str("(round 3.5)"), "4.0");
+    assert_eq!(run_str("(round -2.5)"), "-2.0");
+    assert_eq!(run_str("(round 5/2)"), "2");
+    assert_eq!(run_str("(round 7/2)"), "4");
+    assert_eq!(run_str("(round -7/2)"), "-4");
+    assert_eq!(run_str("(round 8/3)"), "3");
+    assert_eq!(
+        run_str("(round 1+i)"),
+        "Error: expected real number, got: 1+i"
+    );
+}
+
+#[test]
+fn gcd_and_lcm() {
+    assert_eq!(run_str("(gcd)"), "0");
+    assert_eq!(run_str("(lcm)"), "1");
+    assert_eq!(run_str("(gcd 32 -36)"), "4");
+    assert_eq!(run_str("(gcd 12 18 8)"), "2");
+    assert_eq!(run_str("(lcm 32 -36)"), "288");
+    assert_eq!(run_str("(lcm 4 0)"), "0");
+    assert_eq!(run_str("(gcd 4.0 6)"), "2.0");
+    assert_eq!(run_str("(gcd 1.5 3)"), "Error: expected integer, got: 1.5");
+}
+
+#[test]
+fn number_to_string_with_radix() {
+    assert_eq!(run_str("(number->string 255)"), "\"255\"");
+    assert_eq!(run_str("(number->string 255 16)"), "\"ff\"");
+    assert_eq!(run_str("(number->string -5 2)"), "\"-101\"");
+    assert_eq!(run_str("(number->string 3/4 2)"), "\"11/100\"");
+    assert_eq!(
+        run_str("(number->string 1.5 2)"),
+        "Error: inexact number can only be written in radix 10, got: 1.5"
+    );
+    assert_eq!(
+        run_str("(number->string 1 3)"),
+        "Error: expected radix 2, 8, 10 or 16, got: 3"
+    );
+    assert_eq!(
+        run_str("(number->string 1 2 3)"),
+        "Error: function takes 1 or 2 arguments"
+    );
+}
+
+#[test]
+fn string_to_number_with_radix() {
+    assert_eq!(run_str("(string->number \"42\")"), "42");
+    assert_eq!(run_str("(string->number \"ff\" 16)"), "255");
+    assert_eq!(run_str("(string->number \"#xff\")"), "255");
+    assert_eq!(run_str("(string->number \"101\" 2)"), "5");
+    assert_eq!(run_str("(string->number \"1/2\")"), "1/2");
+    assert_eq!(run_str("(string->number \"abc\")"), "false");
+    assert_eq!(run_str("(string->number \"12\" 2)"), "false");
+    assert_eq!(
+        run_str("(string->number (number->string 12345678901234567890 16) 16)"),
+        "12345678901234567890"
+    );
+    assert_eq!(
+        run_str("(float? (string->number (number->string 2.0)))"),
+        "true"
+    );
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{run, run_str};
+
+const THREE_ROLLS: &str = "(cons (random 100) (cons (random 100) (cons (random 100) nil)))";
+
+#[test]
+fn seeded_sequences_are_reproducible() {
+    let rolls = format!("(random-seed! 42) {}", THREE_ROLLS);
+    assert_eq!(run_str(&rolls), run_str(&rolls));
+    assert_eq!(run_str(&rolls), "(42 . (2 . (9 . nil)))");
+    assert_eq!(
+        run_str("(random-seed! 42) (random-float)"),
+        "0.08386297105988216"
+    );
+    assert_eq!(
+        run_str("(random-seed! 7) (shuffle '(1 2 3 4 5 6))"),
+        "(4 . (6 . (2 . (3 . (5 . (1 . nil))))))"
+    );
+}
+
+#[test]
+fn reseeding_restarts_the_sequence() {
+    let code = format!(
+        "(random-seed! 1) (define a {rolls}) (random-seed! 1) (equal? a {rolls})",
+        rolls = THREE_ROLLS
+    );
+    assert_eq!(run_str(&code), "true");
+    let code = format!(
+        "(random-seed! 1) (define a {rolls}) (random-seed! 2) (equal? a {rolls})",
+        rolls = THREE_ROLLS
+    );
+    assert_eq!(run_str(&code), "false");
+}
+
+#[test]
+fn generator_state_is_shared_with_closures() {
+    let code = format!(
+        "(random-seed! 3) (define a {}) (random-seed! 3) \
+         (define (roll) (random 100)) \
+         (equal? a (cons (roll) (cons (roll) (cons (roll) nil))))",
+        THREE_ROLLS
+    );
+    assert_eq!(run_str(&code), "true");
+}
+
+#[test]
+fn results_are_in_range() {
+    let code = "(define (check n) \
+                  (if (zero? n) true \
+                    (if (and (< -1 (random 6) 6) (< -0.1 (random-float) 1)) \
+                      (check (- n 1)) \
+                      false))) \
+                (check 1000)";
+    assert_eq!(run_str(code), "true");
+    assert_eq!(run_str("(random 1)"), "0");
+    assert_eq!(
+        run_str("(random-seed! 42) (random 100000000000000000000000)"),
+        "7191411724426272556860"
+    );
+}
+
+#[test]
+fn shuffle_keeps_elements() {
+    assert_eq!(run_str("(shuffle '())"), "nil");
+    assert_eq!(run_str("(shuffle '(1))"), "(1 . nil)");
+    assert_eq!(
+        run_str("(shuffle '(1 . 2))"),
+        "Error: expected list, got: (1 . 2)"
+    );
+}
+
+#[test]
+fn invalid_arguments() {
+    assert_eq!(
+        run_str("(random 0)"),
+        "Error: expected exact positive integer, got: 0"
+    );
+    assert_eq!(
+        run_str("(random 2.5)"),
+        "Error: expected exact positive integer, got: 2.5"
+    );
+    assert!(run("(random-float 1)").is_err());
+    assert_eq!(
+        run_str("(random-seed! 1.5)"),
+        "Error: expected exact integer seed that fits in 64 bits, got: 1.5"
+    );
+}
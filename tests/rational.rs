@@ -0,0 +1,100 @@
+mod common;
+
+use common::run_str;
+
+#[test]
+fn exact_division() {
+    assert_eq!(run_str("(/ 1 3)"), "1/3");
+    assert_eq!(run_str("(/ 6 4)"), "3/2");
+    assert_eq!(run_str("(/ -6 4)"), "-3/2");
+    assert_eq!(run_str("(/ 6 -4)"), "-3/2");
+    assert_eq!(run_str("(/ 6 3)"), "2");
+    assert_eq!(run_str("(/ 1 3 2)"), "1/6");
+}
+
+#[test]
+fn literals() {
+    assert_eq!(run_str("1/3"), "1/3");
+    assert_eq!(run_str("2/4"), "1/2");
+    assert_eq!(run_str("-2/4"), "-1/2");
+    assert_eq!(run_str("4/2"), "2");
+    assert_eq!(run_str("(int? 4/2)"), "true");
+    assert_eq!(
+        run_str("1/100000000000000000000"),
+        "1/100000000000000000000"
+    );
+    assert_eq!(
+        run_str("'1/-2"),
+        "Error: invalid digit '-' for radix 10 in numeral: 1/-2"
+    );
+    assert_eq!(run_str("(symbol? '/)"), "true");
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(run_str("(+ 1/3 1/6)"), "1/2");
+    assert_eq!(run_str("(+ 1/3 2/3)"), "1");
+    assert_eq!(run_str("(- 1/2 1/3)"), "1/6");
+    assert_eq!(run_str("(* 2/3 3/4)"), "1/2");
+    assert_eq!(run_str("(* 2/3 3/2)"), "1");
+    assert_eq!(run_str("(/ 2/3 4/9)"), "3/2");
+    assert_eq!(run_str("(- 1/3)"), "-1/3");
+    assert_eq!(run_str("(abs -1/3)"), "1/3");
+    assert_eq!(run_str("(+ 1/2 0.25)"), "0.75");
+    assert_eq!(run_str("(/ 1/2 0)"), "Error: division by zero");
+}
+
+#[test]
+fn comparisons() {
+    assert_eq!(run_str("(< 1/3 1/2 1)"), "true");
+    assert_eq!(run_str("(= 1/2 0.5)"), "true");
+    assert_eq!(run_str("(< 1/3 0.3333333333333333)"), "false");
+    assert_eq!(run_str("(> 1/3 0.3333333333333333)"), "true");
+    assert_eq!(run_str("(eqv? 1/2 2/4)"), "true");
+    assert_eq!(run_str("(eqv? 1/2 0.5)"), "false");
+    assert_eq!(run_str("(min 1/2 1/3)"), "1/3");
+    assert_eq!(run_str("(negative? -1/2)"), "true");
+}
+
+#[test]
+fn numerator_and_denominator() {
+    assert_eq!(run_str("(numerator 6/4)"), "3");
+    assert_eq!(run_str("(denominator 6/4)"), "2");
+    assert_eq!(run_str("(numerator -5)"), "-5");
+    assert_eq!(run_str("(denominator -5)"), "1");
+    assert_eq!(run_str("(denominator 0.5)"), "2.0");
+    assert_eq!(run_str("(float? (denominator 0.5))"), "true");
+}
+
+#[test]
+fn exactness_conversion() {
+    assert_eq!(run_str("(exact->inexact 1/4)"), "0.25");
+    assert_eq!(run_str("(exact->inexact 1/3)"), "0.3333333333333333");
+    assert_eq!(run_str("(inexact->exact 0.25)"), "1/4");
+    assert_eq!(run_str("(inexact->exact 3.0)"), "3");
+    assert_eq!(run_str("(inexact->exact 1e20)"), "100000000000000000000");
+    assert_eq!(
+        run_str("(inexact->exact 0.1)"),
+        "3602879701896397/36028797018963968"
+    );
+    assert_eq!(
+        run_str("(inexact->exact (/ 1.0 0.0))"),
+        "Error: no exact representation of: +inf.0"
+    );
+    // Both parts are out of range of floats, but their quotient isn't.
+    let huge = format!(
+        "(exact->inexact (/ 1{}1 3{}))",
+        "0".repeat(399),
+        "0".repeat(400)
+    );
+    assert_eq!(run_str(&huge), "0.3333333333333333");
+}
+
+#[test]
+fn integer_operations_reject_fractions() {
+    assert_eq!(
+        run_str("(quotient 1/2 2)"),
+        "Error: expected integer, got: 1/2"
+    );
+    assert_eq!(run_str("(even? 1/2)"), "Error: expected integer, got: 1/2");
+}
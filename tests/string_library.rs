@@ -0,0 +1,134 @@
+mod common;
+
+use common::{run, run_str};
+
+#[test]
+fn length_and_indexing_count_characters() {
+    assert_eq!(run_str("(string-length \"\")"), "0");
+    assert_eq!(run_str("(string-length \"héllo\")"), "5");
+    assert_eq!(run_str("(string-length \"日本語\")"), "3");
+    assert_eq!(run_str("(string-ref \"日本語\" 1)"), "本");
+    assert_eq!(
+        run_str("(string-ref \"abc\" 3)"),
+        "Error: index 3 out of range for: \"abc\""
+    );
+    assert_eq!(
+        run_str("(string-ref \"abc\" -1)"),
+        "Error: expected non-negative index, got: -1"
+    );
+}
+
+#[test]
+fn substrings_and_concatenation() {
+    assert_eq!(run_str("(substring \"héllo\" 1 3)"), "\"él\"");
+    assert_eq!(run_str("(substring \"héllo\" 2)"), "\"llo\"");
+    assert_eq!(run_str("(substring \"abc\" 3 3)"), "\"\"");
+    assert_eq!(
+        run_str("(substring \"abc\" 2 1)"),
+        "Error: substring start 2 is after its end 1"
+    );
+    assert!(run("(substring \"abc\" 1 4)").is_err());
+    assert_eq!(run_str("(string-append)"), "\"\"");
+    assert_eq!(run_str("(string-append \"ab\" \"\" \"ç\")"), "\"abç\"");
+    assert_eq!(
+        run_str("(string-append \"a\" 'b)"),
+        "Error: expected string, got: b"
+    );
+}
+
+#[test]
+fn searching() {
+    assert_eq!(
+        run_str("(string-index \"día\" (car (string->list \"a\")))"),
+        "2"
+    );
+    assert_eq!(
+        run_str("(string-index \"a1b2\" (lambda (c) (equal? c (string-ref \"2\" 0))))"),
+        "3"
+    );
+    assert_eq!(
+        run_str("(string-index \"abc\" (lambda (c) false))"),
+        "false"
+    );
+    assert_eq!(run_str("(string-contains \"日本語です\" \"語で\")"), "2");
+    assert_eq!(run_str("(string-contains \"abc\" \"\")"), "0");
+    assert_eq!(run_str("(string-contains \"abc\" \"d\")"), "false");
+    assert_eq!(run_str("(string-prefix? \"ab\" \"abc\")"), "true");
+    assert_eq!(run_str("(string-prefix? \"abc\" \"ab\")"), "false");
+    assert_eq!(run_str("(string-suffix? \"ße\" \"straße\")"), "true");
+    assert_eq!(run_str("(string-suffix? \"b\" \"abc\")"), "false");
+}
+
+#[test]
+fn case_conversion() {
+    assert_eq!(run_str("(string-upcase \"straße\")"), "\"STRASSE\"");
+    assert_eq!(run_str("(string-downcase \"ÀÉÎ\")"), "\"àéî\"");
+    assert_eq!(run_str("(string-foldcase \"Straße\")"), "\"strasse\"");
+    assert_eq!(run_str("(string-foldcase \"ΟΔΟΣ\")"), "\"οδοσ\"");
+    assert_eq!(run_str("(string-downcase \"ΟΔΟΣ\")"), "\"οδος\"");
+}
+
+#[test]
+fn splitting_and_joining() {
+    assert_eq!(
+        run_str("(string-split \"  a b\u{3000}c \")"),
+        "(\"a\" . (\"b\" . (\"c\" . nil)))"
+    );
+    assert_eq!(
+        run_str("(string-split \"a,,b\" \",\")"),
+        "(\"a\" . (\"\" . (\"b\" . nil)))"
+    );
+    assert_eq!(
+        run_str("(string-split \"a\" \"\")"),
+        "Error: empty separator"
+    );
+    assert_eq!(run_str("(string-join '(\"a\" \"b\" \"c\"))"), "\"a b c\"");
+    assert_eq!(run_str("(string-join '(\"a\" \"b\") \"→\")"), "\"a→b\"");
+    assert_eq!(run_str("(string-join '())"), "\"\"");
+    assert_eq!(
+        run_str("(string-join (string-split \"x-y-z\" \"-\") \"+\")"),
+        "\"x+y+z\""
+    );
+}
+
+#[test]
+fn trimming_and_replacing() {
+    assert_eq!(run_str("(string-trim \"\u{a0} hi \t\")"), "\"hi\"");
+    assert_eq!(run_str("(string-trim-left \"  hi  \")"), "\"hi  \"");
+    assert_eq!(run_str("(string-trim-right \"  hi  \")"), "\"  hi\"");
+    assert_eq!(
+        run_str("(string-replace \"a-b-c\" \"-\" \"--\")"),
+        "\"a--b--c\""
+    );
+    assert_eq!(
+        run_str("(string-replace \"日本\" \"本\" \"曜\")"),
+        "\"日曜\""
+    );
+    assert_eq!(
+        run_str("(string-replace \"abc\" \"\" \"x\")"),
+        "Error: empty string to replace"
+    );
+}
+
+#[test]
+fn comparisons() {
+    assert_eq!(run_str("(string=? \"abc\" \"abc\" \"abc\")"), "true");
+    assert_eq!(run_str("(string=? \"abc\" \"abd\")"), "false");
+    assert_eq!(run_str("(string<? \"abc\" \"abd\" \"b\")"), "true");
+    assert_eq!(run_str("(string<? \"ab\" \"abc\")"), "true");
+    assert_eq!(run_str("(string<? \"z\" \"é\")"), "true");
+    assert_eq!(run_str("(string>? \"b\" \"a\")"), "true");
+    assert_eq!(run_str("(string<=? \"a\" \"a\" \"b\")"), "true");
+    assert_eq!(run_str("(string>=? \"a\" \"b\")"), "false");
+    assert_eq!(run_str("(string-ci=? \"Straße\" \"STRASSE\")"), "true");
+    assert_eq!(run_str("(string-ci<? \"apple\" \"Banana\")"), "true");
+    assert_eq!(run_str("(string<? \"apple\" \"Banana\")"), "false");
+    assert_eq!(
+        run_str("(string=? \"a\" 1)"),
+        "Error: expected string, got: 1"
+    );
+    assert_eq!(
+        run_str("(string=?)"),
+        "Error: function takes at least 1 argument"
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::{run, run_str};
+use plib::sexp::{Atom, Sexp};
+
+fn string(s: &str) -> Sexp {
+    Sexp::Atom(Atom::String(s.into()))
+}
+
+#[test]
+fn string_literals_evaluate_to_themselves() {
+    assert_eq!(run_str("\"abc\""), "\"abc\"");
+    assert_eq!(run_str("'\"abc\""), "\"abc\"");
+    assert_eq!(run_str("\"\""), "\"\"");
+    assert_eq!(run_str("\"héllo wörld\""), "\"héllo wörld\"");
+    assert_eq!(run_str("'(\"a\" \"b\")"), "(\"a\" . (\"b\" . nil))");
+}
+
+#[test]
+fn writer_escapes_and_display_does_not() {
+    let s = string("say \"hi\"\\\n\t\u{7}");
+    assert_eq!(s.to_string(), "\"say \\\"hi\\\"\\\\\\n\\t\\u{7}\"");
+    assert_eq!(s.display().to_string(), "say \"hi\"\\\n\t\u{7}");
+    let list = Sexp::list(vec![string("a b"), Sexp::Atom(Atom::Char('c'))]);
+    assert_eq!(list.display().to_string(), "(a b . (c . nil))");
+    assert_eq!(run_str("(display \"hi\")"), "nil");
+}
+
+#[test]
+fn string_predicate() {
+    assert_eq!(run_str("(string? \"abc\")"), "true");
+    assert_eq!(run_str("(string? \"\")"), "true");
+    assert_eq!(run_str("(string? 'abc)"), "false");
+    assert_eq!(run_str("(string? '(a))"), "false");
+    assert_eq!(run_str("(atom? \"abc\")"), "true");
+}
+
+#[test]
+fn conversion_to_and_from_lists() {
+    assert_eq!(run_str("(string->list \"abc\")"), "(a . (b . (c . nil)))");
+    assert_eq!(run_str("(string->list \"\")"), "nil");
+    assert_eq!(run_str("(length (string->list \"日本語\"))"), "3");
+    assert_eq!(
+        run_str("(list->string (reverse (string->list \"héllo\")))"),
+        "\"olléh\""
+    );
+    assert_eq!(run_str("(list->string '())"), "\"\"");
+    assert_eq!(
+        run_str("(list->string '(1 2))"),
+        "Error: expected character, got: 1"
+    );
+    assert_eq!(
+        run_str("(string->list 'a)"),
+        "Error: expected string, got: a"
+    );
+}
+
+#[test]
+fn equality() {
+    assert_eq!(run_str("(equal? \"abc\" \"abc\")"), "true");
+    assert_eq!(run_str("(equal? \"abc\" \"abd\")"), "false");
+    assert_eq!(run_str("(eqv? \"abc\" \"abc\")"), "false");
+    assert_eq!(run_str("(define s \"abc\") (eq? s s)"), "true");
+    assert_eq!(run_str("(member \"b\" '(\"a\" \"b\"))"), "(\"b\" . nil)");
+    assert_eq!(run_str("(equal? \"abc\" 'abc)"), "false");
+}
+
+#[test]
+fn string_literals_in_syntax_rules_patterns() {
+    let code = "(define-syntax greet (syntax-rules () ((_ \"hi\") 'hello) ((_ x) 'other))) \
+                (list (greet \"hi\") (greet \"bye\"))";
+    assert_eq!(run_str(code), "(hello . (other . nil))");
+    assert!(run("(\"abc\")").is_err());
+}
+
+#[test]
+fn written_strings_read_back() {
+    for text in [
+        "plain",
+        "q\"uote",
+        "back\\slash",
+        "tab\tnew\nline\r",
+        "nul\0bell\u{7}",
+    ] {
+        let written = string(text).to_string();
+        assert_eq!(run_str(&written), written);
+        assert_eq!(
+            run_str(&format!("(string-length {})", written)),
+            text.chars().count().to_string()
+        );
+    }
+}
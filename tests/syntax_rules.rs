@@ -0,0 +1,142 @@
+mod common;
+
+use common::{run, run_str};
+
+#[test]
+fn syntax_rules_substitutes_pattern_variables() {
+    let code = "
+        (define-syntax my-if
+          (syntax-rules ()
+            ((_ c a b) (cond (c a) (else b)))))
+        (my-if false (undefined) 'no)";
+    assert_eq!(run_str(code), "no");
+}
+
+#[test]
+fn introduced_bindings_do_not_capture_user_symbols() {
+    let code = "
+        (define-syntax my-or
+          (syntax-rules ()
+            ((_ a b) (let ((t a)) (if t t b)))))
+        (define t 'user)
+        (my-or false t)";
+    assert_eq!(run_str(code), "user");
+}
+
+#[test]
+fn free_template_symbols_resolve_where_macro_is_defined() {
+    let defs = "
+        (define-syntax m
+          (syntax-rules ()
+            ((_ x) (cons x 1))))";
+    assert_eq!(
+        run_str(&format!("{} (let ((cons list)) (m 2))", defs)),
+        "(2 . 1)"
+    );
+    let code = "
+        (define (f y)
+          (define-syntax get-y
+            (syntax-rules ()
+              ((_) y)))
+          (let ((y 'inner)) (get-y)))
+        (f 'outer)";
+    assert_eq!(run_str(code), "outer");
+}
+
+#[test]
+fn hygienic_swap() {
+    let code = "
+        (define-syntax swap!
+          (syntax-rules ()
+            ((_ a b) (let ((tmp a)) (set! a b) (set! b tmp)))))
+        (define tmp 1)
+        (define other 2)
+        (swap! tmp other)
+        (cons tmp other)";
+    assert_eq!(run_str(code), "(2 . 1)");
+}
+
+#[test]
+fn recursive_ellipsis_rules() {
+    let defs = "
+        (define-syntax my-and
+          (syntax-rules ()
+            ((_) true)
+            ((_ e) e)
+            ((_ e r ...) (if e (my-and r ...) false))))";
+    assert_eq!(run_str(&format!("{} (my-and)", defs)), "true");
+    assert_eq!(run_str(&format!("{} (my-and 1 2 'c)", defs)), "c");
+    assert_eq!(
+        run_str(&format!("{} (my-and 1 false (undefined))", defs)),
+        "false"
+    );
+}
+
+#[test]
+fn literals_match_only_themselves() {
+    let defs = "
+        (define-syntax my-cond
+          (syntax-rules (else)
+            ((_ (else e)) e)
+            ((_ (c e) rest ...) (if c e (my-cond rest ...)))))";
+    assert_eq!(
+        run_str(&format!("{} (my-cond (false 1) (else 'x))", defs)),
+        "x"
+    );
+    assert!(run(&format!("{} (my-cond (false 1) (other 'x))", defs)).is_err());
+}
+
+#[test]
+fn nested_ellipsis() {
+    let code = "
+        (define-syntax flip
+          (syntax-rules ()
+            ((_ (a b ...) ...) '((b ... a) ...))))
+        (flip (1 2 3) (4 5))";
+    assert_eq!(run_str(code), run_str("'((2 3 1) (5 4))"));
+}
+
+#[test]
+fn quoted_template_symbols_are_not_renamed() {
+    let code = "
+        (define-syntax name-of
+          (syntax-rules ()
+            ((_ x) '(name x))))
+        (name-of y)";
+    assert_eq!(run_str(code), "(name . (y . nil))");
+}
+
+#[test]
+fn quasiquoted_template_symbols_are_not_renamed() {
+    let defs = "
+        (define-syntax q
+          (syntax-rules ()
+            ((_ x) `(a ,x))))
+        (define-syntax r
+          (syntax-rules ()
+            ((_ x) `(a ,(let ((t x)) t) ,@(list 'b x)))))";
+    assert_eq!(run_str(&format!("{} (eq? (car (q 1)) 'a)", defs)), "true");
+    assert_eq!(
+        run_str(&format!("{} (define t 'user) (r t)", defs)),
+        "(a . (user . (b . (user . nil))))"
+    );
+}
+
+#[test]
+fn custom_ellipsis() {
+    let code = "
+        (define-syntax my-list
+          (syntax-rules ::: ()
+            ((_ x :::) '(x :::))))
+        (my-list 1 2)";
+    assert_eq!(run_str(code), "(1 . (2 . nil))");
+}
+
+#[test]
+fn syntax_rules_errors() {
+    let defs = "(define-syntax one (syntax-rules () ((_ x) x)))";
+    assert!(run(&format!("{} (one)", defs)).is_err());
+    assert!(run("(define-syntax bad (syntax-rules () (_ x)))").is_err());
+    assert!(run("(define-syntax bad 1)").is_err());
+    assert!(run("(define-syntax bad (syntax-rules () ((_ x ...) x))) (bad 1)").is_err());
+}
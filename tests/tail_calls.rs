@@ -0,0 +1,130 @@
+mod common;
+
+use common::run_in;
+use plib::env::Env;
+use plib::number::Number;
+use plib::sexp::{Atom, Error, Sexp};
+use std::thread;
+
+const STACK_SIZE: usize = 256 * 1024;
+
+fn int_args(args: &Sexp) -> Vec<i32> {
+    args.into_iter()
+        .map(|arg| match arg {
+            Sexp::Atom(Atom::Number(Number::Int(i))) => *i,
+            _ => panic!("expected integer, got: {}", arg),
+        })
+        .collect()
+}
+
+fn inc(args: &Sexp) -> Result<Sexp, Error> {
+    Ok(Sexp::Atom(Atom::Number(Number::Int(int_args(args)[0] + 1))))
+}
+
+fn same(args: &Sexp) -> Result<Sexp, Error> {
+    let args = int_args(args);
+    Ok(Sexp::Atom(Atom::Bool(args[0] == args[1])))
+}
+
+/// Runs `code` on a thread with a small stack, so any stack growth
+/// proportional to the iteration count overflows it.
+fn run_small_stack(code: &'static str) -> String {
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let env = Env::default_env();
+            env.insert(
+                "inc".to_owned(),
+                Sexp::Atom(Atom::Func {
+                    fun: inc,
+                    name: "inc",
+                }),
+            );
+            env.insert(
+                "same?".to_owned(),
+                Sexp::Atom(Atom::Func {
+                    fun: same,
+                    name: "same?",
+                }),
+            );
+            match run_in(code, &env) {
+                Ok(sexp) => sexp.to_string(),
+                Err(err) => err.to_string(),
+            }
+        })
+        .unwrap()
+        .join()
+        .unwrap()
+}
+
+#[test]
+fn named_let_counts_in_constant_stack() {
+    let code = "
+        (let loop ((i 0))
+          (if (same? i 100000)
+              i
+              (loop (inc i))))";
+    assert_eq!(run_small_stack(code), "100000");
+}
+
+#[test]
+fn named_let_counts_to_one_million() {
+    let code = "
+        (let loop ((i 0))
+          (if (same? i 1000000)
+              i
+              (loop (inc i))))";
+    assert_eq!(run_small_stack(code), "1000000");
+}
+
+// Takes about a minute without optimizations, run with
+// `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn named_let_counts_to_ten_million() {
+    let code = "
+        (let loop ((i 0))
+          (if (same? i 10000000)
+              i
+              (loop (inc i))))";
+    assert_eq!(run_small_stack(code), "10000000");
+}
+
+#[test]
+fn tail_positions_in_special_forms() {
+    let code = "
+        (define (count i)
+          (cond ((same? i 10000) i)
+                (else (let ((next (inc i)))
+                        (when true
+                          (and true (or false (count next))))))))
+        (count 0)";
+    assert_eq!(run_small_stack(code), "10000");
+}
+
+#[test]
+fn mutual_tail_recursion() {
+    let code = "
+        (define (ping i) (if (same? i 10000) 'done (pong (inc i))))
+        (define (pong i) (unless false (ping i)))
+        (ping 0)";
+    assert_eq!(run_small_stack(code), "done");
+}
+
+#[test]
+fn long_lists_are_built_printed_and_freed_in_constant_stack() {
+    let code = "
+        (let loop ((i 0) (acc nil))
+          (if (< i 100000)
+              (loop (+ i 1) (cons i acc))
+              'built))";
+    assert_eq!(run_small_stack(code), "built");
+    let code = "
+        (let loop ((i 0) (acc nil))
+          (if (< i 100000)
+              (loop (+ i 1) (cons i acc))
+              acc))";
+    let printed = run_small_stack(code);
+    assert!(printed.starts_with("(99999 . (99998 . "));
+    assert!(printed.ends_with(&format!("(0 . nil{}", ")".repeat(100000))));
+}